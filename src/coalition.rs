@@ -0,0 +1,375 @@
+//! Analysis of coalitions in a [weighted voting game][wp-game], where set `i`
+//! has the weight `values[i]`, and a coalition wins if its weights sum to at
+//! least a threshold.
+//!
+//! This is the same classification used for the circles around the pie charts,
+//! see [`DiagramConfig::thresholds`](crate::svg::DiagramConfig::thresholds).
+//!
+//! ```
+//! use venn_diagrams::coalition::{Coalition, Game};
+//!
+//! let game = Game::new(vec![0.3, 0.3, 0.4], 0.5);
+//! assert_eq!(game.classify(0b001), Coalition::Below);
+//! assert_eq!(game.classify(0b011), Coalition::Edge);
+//! assert_eq!(game.classify(0b111), Coalition::Above);
+//! assert_eq!(game.minimal_winning(), vec![0b011, 0b101, 0b110]);
+//! assert_eq!(game.banzhaf(), vec![1.0 / 3.0; 3]);
+//! ```
+//!
+//! Coalitions are represented as a [`SetMask`]. With integer weights, such as
+//! the seats of parties in a parliament, [`Seats`] decides which coalitions
+//! win without rounding errors.
+//!
+//! [wp-game]: https://en.wikipedia.org/wiki/Weighted_voting
+
+use crate::diagram::SetMask;
+
+/// Classification of a coalition, see [`Game::classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Coalition {
+    /// The coalition doesn't win.
+    Below,
+
+    /// The coalition wins, but removing any set causes it to lose, i.e. it's
+    /// a minimal winning coalition.
+    Edge,
+
+    /// The coalition wins, even if some set is removed.
+    Above,
+}
+
+/// A weighted voting game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    values: Vec<f64>,
+    threshold: f64,
+}
+
+impl Game {
+    /// Create a game where set `i` has the weight `values[i]`, and a
+    /// coalition wins if its weights sum to at least `threshold`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more sets than bits in a [`SetMask`].
+    #[must_use]
+    pub fn new(values: Vec<f64>, threshold: f64) -> Self {
+        assert!(values.len() <= SetMask::BITS as usize, "Too many sets");
+        Self { values, threshold }
+    }
+
+    /// Number of sets.
+    #[must_use]
+    pub fn n(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Weight of each set.
+    #[must_use]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// The smallest sum of weights of a winning coalition.
+    #[must_use]
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    // Every member of a coalition, in increasing order
+    fn members(&self, coalition: SetMask) -> impl Iterator<Item = usize> {
+        (0..self.n()).filter(move |i| coalition & (1 << i) != 0)
+    }
+
+    /// The sum of the weights of the members of `coalition`.
+    #[must_use]
+    pub fn total(&self, coalition: SetMask) -> f64 {
+        self.members(coalition).map(|i| self.values[i]).sum()
+    }
+
+    /// Returns `true` if the weights of `coalition` sum to at least the
+    /// threshold.
+    #[must_use]
+    pub fn is_winning(&self, coalition: SetMask) -> bool {
+        self.total(coalition) >= self.threshold
+    }
+
+    /// Returns `true` if the sets outside of `coalition` don't win, i.e. the
+    /// coalition can prevent every other coalition from winning.
+    #[must_use]
+    pub fn is_blocking(&self, coalition: SetMask) -> bool {
+        !self.is_winning(self.all() & !coalition)
+    }
+
+    /// Classify `coalition` by whether it wins, and if so, whether it needs
+    /// every member to win.
+    #[must_use]
+    pub fn classify(&self, coalition: SetMask) -> Coalition {
+        let total = self.total(coalition);
+        if total < self.threshold {
+            Coalition::Below
+        } else if self.members(coalition).all(|i| total - self.values[i] < self.threshold) {
+            Coalition::Edge
+        } else {
+            Coalition::Above
+        }
+    }
+
+    // The coalition of every set
+    fn all(&self) -> SetMask {
+        if self.n() == 0 { 0 } else { SetMask::MAX >> (SetMask::BITS as usize - self.n()) }
+    }
+
+    // Every non-empty coalition satisfying `f`, in increasing order
+    fn coalitions(&self, f: impl Fn(SetMask) -> bool) -> Vec<SetMask> {
+        (1..=self.all()).filter(|&coalition| f(coalition)).collect()
+    }
+
+    /// Every non-empty winning coalition.
+    #[must_use]
+    pub fn winning(&self) -> Vec<SetMask> {
+        self.coalitions(|coalition| self.is_winning(coalition))
+    }
+
+    /// Every non-empty minimal winning coalition, i.e. those classified as
+    /// [`Coalition::Edge`].
+    #[must_use]
+    pub fn minimal_winning(&self) -> Vec<SetMask> {
+        self.coalitions(|coalition| self.classify(coalition) == Coalition::Edge)
+    }
+
+    /// Every non-empty blocking coalition, see [`Game::is_blocking`].
+    #[must_use]
+    pub fn blocking(&self) -> Vec<SetMask> {
+        self.coalitions(|coalition| self.is_blocking(coalition))
+    }
+
+    // Calls `f(i, coalition)` for every coalition which loses, but wins when
+    // set `i` joins
+    fn swings(&self, mut f: impl FnMut(usize, SetMask)) {
+        for coalition in 0..=self.all() {
+            if self.is_winning(coalition) {
+                continue;
+            }
+            for i in 0..self.n() {
+                if coalition & (1 << i) == 0 && self.is_winning(coalition | (1 << i)) {
+                    f(i, coalition);
+                }
+            }
+        }
+    }
+
+    /// The normalized [Banzhaf power index][wp-banzhaf] of each set, i.e. the
+    /// share of the swings where the set turns a losing coalition into a
+    /// winning one. Every index is zero if there are no swings.
+    ///
+    /// [wp-banzhaf]: https://en.wikipedia.org/wiki/Banzhaf_power_index
+    #[must_use]
+    pub fn banzhaf(&self) -> Vec<f64> {
+        let mut counts = vec![0_usize; self.n()];
+        self.swings(|i, _| counts[i] += 1);
+        let total: usize = counts.iter().sum();
+        counts
+            .into_iter()
+            .map(|count| if total == 0 { 0.0 } else { count as f64 / total as f64 })
+            .collect()
+    }
+
+    /// The [Shapley–Shubik power index][wp-shapley] of each set, i.e. the
+    /// share of the orderings of the sets where the set is the first to make
+    /// the sets before it a winning coalition.
+    ///
+    /// [wp-shapley]: https://en.wikipedia.org/wiki/Shapley%E2%80%93Shubik_power_index
+    #[must_use]
+    pub fn shapley_shubik(&self) -> Vec<f64> {
+        let n = self.n();
+        // The share of orderings starting with a given coalition of size k,
+        // followed by a given set: k! (n - k - 1)! / n!
+        let mut weights = vec![0.0; n];
+        for (k, weight) in weights.iter_mut().enumerate() {
+            *weight = (1..=k).map(|x| x as f64).product::<f64>()
+                * (1..n - k).map(|x| x as f64).product::<f64>()
+                / (1..=n).map(|x| x as f64).product::<f64>();
+        }
+
+        let mut index = vec![0.0; n];
+        self.swings(|i, coalition| index[i] += weights[coalition.count_ones() as usize]);
+        index
+    }
+}
+
+/// Integer seat counts of the sets, e.g. the parties in a parliament.
+///
+/// The sets don't have to hold every seat, but a coalition needs a share of
+/// the total number of seats to win. Seats are counted exactly, so a coalition
+/// with 175 of 350 seats has exactly half of them.
+///
+/// ```
+/// use venn_diagrams::coalition::{Coalition, Seats};
+///
+/// let seats = Seats::new(vec![175, 100, 75]);
+/// assert_eq!(seats.quota(0.5), 175);
+/// assert_eq!(seats.game(0.5).classify(0b110), Coalition::Edge);
+/// assert_eq!(seats.fractions(), vec![0.5, 100.0 / 350.0, 75.0 / 350.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seats {
+    counts: Vec<u64>,
+    total: u64,
+}
+
+impl Seats {
+    /// Create seat counts where the sets hold every seat.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no seats, or more sets than bits in a [`SetMask`].
+    #[must_use]
+    pub fn new(counts: Vec<u64>) -> Self {
+        let total = counts.iter().sum();
+        Self::with_total(counts, total)
+    }
+
+    /// Create seat counts out of `total` seats, where the remaining seats
+    /// belong to no set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is zero or less than the sum of `counts`, or if there
+    /// are more sets than bits in a [`SetMask`].
+    #[must_use]
+    pub fn with_total(counts: Vec<u64>, total: u64) -> Self {
+        assert!(counts.len() <= SetMask::BITS as usize, "Too many sets");
+        assert!(total > 0, "No seats");
+        assert!(counts.iter().sum::<u64>() <= total, "More seats than the total");
+        Self { counts, total }
+    }
+
+    /// Number of seats of each set.
+    #[must_use]
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Total number of seats.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Number of seats held by the members of `coalition`.
+    #[must_use]
+    pub fn seats(&self, coalition: SetMask) -> u64 {
+        let members = self.counts.iter().enumerate().filter(|(i, _)| coalition & (1 << i) != 0);
+        members.map(|(_, count)| count).sum()
+    }
+
+    /// The share of the seats of each set, which can be used as the values of
+    /// a diagram.
+    #[must_use]
+    pub fn fractions(&self) -> Vec<f64> {
+        self.counts.iter().map(|&count| count as f64 / self.total as f64).collect()
+    }
+
+    /// The smallest number of seats whose share of the total is at least
+    /// `threshold`, or one more than the total if there is no such number.
+    #[must_use]
+    pub fn quota(&self, threshold: f64) -> u64 {
+        let total = self.total as f64;
+        let wins = |seats: u64| seats as f64 / total >= threshold;
+        let mut quota = (threshold * total).ceil().clamp(0.0, total + 1.0) as u64;
+        while quota > 0 && wins(quota - 1) {
+            quota -= 1;
+        }
+        while quota <= self.total && !wins(quota) {
+            quota += 1;
+        }
+        quota
+    }
+
+    /// The game where a coalition wins if its share of the seats is at least
+    /// `threshold`, using the seat counts as weights and the
+    /// [quota](Seats::quota) as threshold.
+    #[must_use]
+    pub fn game(&self, threshold: f64) -> Game {
+        let values = self.counts.iter().map(|&count| count as f64).collect();
+        Game::new(values, self.quota(threshold) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-9, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn classify() {
+        let game = Game::new(vec![5.0, 3.0, 2.0], 5.0);
+        assert_eq!(game.classify(0b000), Coalition::Below);
+        assert_eq!(game.classify(0b001), Coalition::Edge);
+        assert_eq!(game.classify(0b110), Coalition::Edge);
+        assert_eq!(game.classify(0b011), Coalition::Above);
+        assert_eq!(game.classify(0b010), Coalition::Below);
+
+        assert_eq!(game.winning(), vec![0b001, 0b011, 0b101, 0b110, 0b111]);
+        assert_eq!(game.minimal_winning(), vec![0b001, 0b110]);
+        assert_eq!(game.blocking(), vec![0b011, 0b101, 0b111]);
+
+        // With a supermajority, losing coalitions can block
+        let game = Game::new(vec![5.0, 3.0, 2.0], 20.0 / 3.0);
+        assert_eq!(game.minimal_winning(), vec![0b011, 0b101]);
+        assert!(game.is_blocking(0b001));
+        assert!(game.is_blocking(0b110));
+        assert!(!game.is_blocking(0b010));
+    }
+
+    #[test]
+    fn power_indices() {
+        // A dictator has all the power
+        let game = Game::new(vec![0.6, 0.3, 0.1], 0.5);
+        assert_close(&game.banzhaf(), &[1.0, 0.0, 0.0]);
+        assert_close(&game.shapley_shubik(), &[1.0, 0.0, 0.0]);
+
+        // The first set wins with any other set, the others only together
+        let game = Game::new(vec![4.0, 2.0, 1.0, 1.0], 5.0);
+        assert_close(&game.banzhaf(), &[0.7, 0.1, 0.1, 0.1]);
+        assert_close(&game.shapley_shubik(), &[0.75, 1.0 / 12.0, 1.0 / 12.0, 1.0 / 12.0]);
+
+        // Nobody can win
+        let game = Game::new(vec![0.5, 0.5], 2.0);
+        assert_close(&game.banzhaf(), &[0.0, 0.0]);
+        assert_close(&game.shapley_shubik(), &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn seats() {
+        // The Swedish parliament after the 2022 election
+        let seats = Seats::new(vec![107, 73, 68, 24, 24, 19, 18, 16]);
+        assert_eq!(seats.total(), 349);
+        assert_eq!(seats.quota(0.5), 175);
+        assert_eq!(seats.quota(2.0 / 3.0), 233);
+        assert_eq!(seats.seats(0b1010_0110), 176);
+        assert_eq!(seats.game(0.5).classify(0b1010_0110), Coalition::Edge);
+
+        // The fractions of exactly half of the seats sum to less than one half
+        let seats = Seats::new(vec![3, 29, 18, 50]);
+        assert_eq!(Game::new(seats.fractions(), 0.5).classify(0b0111), Coalition::Below);
+        assert_eq!(seats.game(0.5).classify(0b0111), Coalition::Edge);
+
+        assert_eq!(Seats::with_total(vec![1, 2], 300).quota(2.0 / 3.0), 200);
+        assert_eq!(Seats::new(vec![1, 2]).quota(1.5), 4);
+        assert_eq!(Seats::new(vec![1, 2]).quota(-1.0), 0);
+    }
+
+    #[test]
+    #[should_panic = "Too many sets"]
+    fn too_many_parties() {
+        let _ = Seats::new(vec![1; SetMask::BITS as usize + 1]);
+    }
+}
@@ -0,0 +1,165 @@
+//! Generating Rust code for Venn diagrams and their layouts.
+//!
+//! This is how the [premade diagrams](crate::constants) were created, and it
+//! can be used to store an optimized layout as a constant instead of running
+//! the optimization every time.
+//!
+//! ```
+//! use venn_diagrams::{codegen, diagram::Diagram};
+//!
+//! let diagram = Diagram::from_letters("A AB B").unwrap();
+//! let code = codegen::module("TWO", "LAYOUT_TWO", &diagram.layout_greedy()).unwrap();
+//! assert!(code.contains("pub const TWO: DiagramConst<2, 3, 1>"));
+//! assert!(code.contains("pub const LAYOUT_TWO: LayoutConst<8, 2, 3, 1>"));
+//! ```
+
+use std::{error::Error, fmt, fmt::Write};
+
+#[cfg(feature = "optimize")]
+use crate::diagram::Diagram;
+use crate::svg::Layout;
+
+/// Error returned when code can't be generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodegenError {
+    /// A constant name which isn't an uppercase Rust identifier, such as
+    /// `LAYOUT_SIX`.
+    InvalidName {
+        /// The invalid name.
+        name: String,
+    },
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName { name } => {
+                write!(f, "{name:?} isn't a valid name of a constant")
+            }
+        }
+    }
+}
+
+impl Error for CodegenError {}
+
+fn check_name(name: &str) -> Result<(), CodegenError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if valid { Ok(()) } else { Err(CodegenError::InvalidName { name: name.to_string() }) }
+}
+
+/// Create a Rust module containing the diagram of `layout` as the
+/// [`DiagramConst`](crate::diagram::DiagramConst) `diagram_name`, and the
+/// layout itself as the [`LayoutConst`](crate::svg::LayoutConst)
+/// `layout_name`.
+///
+/// The module is formatted by rustfmt, and the long arrays are marked with
+/// `#[rustfmt::skip]`.
+///
+/// Returns an error if a name isn't an uppercase identifier.
+pub fn module(
+    diagram_name: &str,
+    layout_name: &str,
+    layout: &Layout,
+) -> Result<String, CodegenError> {
+    check_name(diagram_name)?;
+    check_name(layout_name)?;
+
+    let n = layout.n();
+    let (x, y) = (layout.width, layout.height);
+    let l: usize = layout.combined_paths.iter().map(Vec::len).sum();
+
+    let combined_paths: Vec<_> = layout.combined_paths.iter().flatten().collect();
+    let offsets: Vec<_> = layout.offsets.iter().flatten().collect();
+    let parts_len: Vec<_> = layout.offsets.iter().map(Vec::len).collect();
+
+    // Writing to a `String` never fails
+    let mut out = String::new();
+    let w = &mut out;
+    writeln!(w, "//! Venn diagram for {n} sets.").unwrap();
+    writeln!(w, "//!").unwrap();
+    writeln!(
+        w,
+        "//! The diagram itself is [`{diagram_name}`], and its layout is [`{layout_name}`]."
+    )
+    .unwrap();
+    writeln!(w).unwrap();
+    writeln!(w, "use venn_diagrams::{{").unwrap();
+    writeln!(w, "    diagram::DiagramConst,").unwrap();
+    writeln!(w, "    svg::{{").unwrap();
+    writeln!(w, "        DirectedEdge::{{Horizontal, Vertical}},").unwrap();
+    writeln!(w, "        LayoutConst,").unwrap();
+    writeln!(w, "    }},").unwrap();
+    writeln!(w, "}};").unwrap();
+    writeln!(w).unwrap();
+    writeln!(w, "/// A Venn diagram for {n} sets.").unwrap();
+    // Break the line like rustfmt, if it's too long
+    let diagram_const = format!("pub const {diagram_name}: DiagramConst<{n}, {x}, {y}> =");
+    let from_grids = "DiagramConst::from_binary_str(GRIDS);";
+    if diagram_const.len() + 1 + from_grids.len() <= 100 {
+        writeln!(w, "{diagram_const} {from_grids}").unwrap();
+    } else {
+        writeln!(w, "{diagram_const}\n    {from_grids}").unwrap();
+    }
+    writeln!(w).unwrap();
+    writeln!(w, "#[rustfmt::skip]").unwrap();
+    writeln!(w, "const GRIDS: [[&str; {y}]; {n}] = [").unwrap();
+    for poly in &layout.diagram.polyominos {
+        writeln!(w, "    [").unwrap();
+        for j in 0..y {
+            let row: String = (0..x).map(|i| if poly[(i, j)] { '1' } else { '0' }).collect();
+            writeln!(w, "        \"{row}\",").unwrap();
+        }
+        writeln!(w, "    ],").unwrap();
+    }
+    writeln!(w, "];").unwrap();
+    writeln!(w).unwrap();
+    writeln!(w, "/// A layout for [`{diagram_name}`].").unwrap();
+    writeln!(w, "#[rustfmt::skip]").unwrap();
+    writeln!(w, "pub const {layout_name}: LayoutConst<{l}, {n}, {x}, {y}> = LayoutConst::new(")
+        .unwrap();
+    writeln!(w, "    {combined_paths:?},").unwrap();
+    writeln!(w, "    {offsets:?},").unwrap();
+    writeln!(w, "    {parts_len:?},").unwrap();
+    writeln!(w, "    {diagram_name},").unwrap();
+    writeln!(w, ");").unwrap();
+
+    Ok(out)
+}
+
+/// Optimize the layout of `diagram` using [`Diagram::layout_optimize`], and
+/// create a module using [`module`] where the layout is called
+/// `LAYOUT_OPTIMIZED_{name}`, like the [premade diagrams](crate::constants).
+#[cfg(feature = "optimize")]
+pub fn optimized_module(name: &str, diagram: Diagram) -> Result<String, CodegenError> {
+    check_name(name)?;
+    module(name, &format!("LAYOUT_OPTIMIZED_{name}"), &diagram.layout_optimize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::d3::LAYOUT_OPTIMIZED_THREE;
+
+    #[test]
+    fn same_as_premade() {
+        let code =
+            module("THREE", "LAYOUT_OPTIMIZED_THREE", &LAYOUT_OPTIMIZED_THREE.into()).unwrap();
+        let premade = include_str!("constants/d3.rs");
+        let layout_start = premade.find("#[rustfmt::skip]\npub const LAYOUT").unwrap();
+        let layout_end = premade[layout_start..].find(");\n").unwrap() + 3;
+        let premade_layout = &premade[layout_start..(layout_start + layout_end)];
+        assert!(code.contains(premade_layout.trim()));
+    }
+
+    #[test]
+    fn invalid_name() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        for name in ["", "three", "3", "THREE-A", "ÄÖ"] {
+            let error = CodegenError::InvalidName { name: String::from(name) };
+            assert_eq!(module(name, "LAYOUT", &layout), Err(error.clone()));
+            assert_eq!(module("THREE", name, &layout), Err(error));
+        }
+    }
+}
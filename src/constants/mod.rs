@@ -12,39 +12,41 @@ pub mod d6;
 pub mod d7;
 pub mod d8;
 
-use std::fmt::Write;
-
-use crate::svg::Layout;
-
-// Function used to create a `PathLayoutConst` from a `PathLayout`.
-#[doc(hidden)]
-pub fn path_layout_to_const(
-    variable_name: &str,
-    path_layout: Layout,
-    diagram_name: &str,
-) -> Option<String> {
-    let l: usize = path_layout.combined_paths.iter().map(Vec::len).sum();
-    let k: usize = path_layout.combined_paths.len();
-    let x: usize = path_layout.width;
-    let y: usize = path_layout.height;
-
-    let combined_paths: Vec<_> = path_layout.combined_paths.iter().flatten().collect();
-    let offsets: Vec<_> = path_layout.offsets.iter().flatten().collect();
-    let parts_len: Vec<_> = path_layout.offsets.iter().map(Vec::len).collect();
-
-    let mut out = String::new();
-
-    writeln!(&mut out, "#[rustfmt::skip]").ok()?;
-    writeln!(
-        &mut out,
-        "pub const {variable_name}: LayoutConst<{l}, {k}, {x}, {y}> = LayoutConst::new("
-    )
-    .ok()?;
-    writeln!(&mut out, "    {combined_paths:?},").ok()?;
-    writeln!(&mut out, "    {offsets:?},").ok()?;
-    writeln!(&mut out, "    {parts_len:?},").ok()?;
-    writeln!(&mut out, "    {diagram_name},").ok()?;
-    writeln!(&mut out, ");").ok()?;
+use crate::{diagram::Diagram, svg::Layout};
+
+/// The premade Venn diagram for `n` sets, if there is one.
+///
+/// ```
+/// use venn_diagrams::constants;
+///
+/// assert_eq!(constants::diagram(3), Some(constants::d3::THREE.into()));
+/// assert_eq!(constants::diagram(9), None);
+/// ```
+#[must_use]
+pub fn diagram(n: usize) -> Option<Diagram> {
+    match n {
+        2 => Some(d2::TWO.into()),
+        3 => Some(d3::THREE.into()),
+        4 => Some(d4::FOUR.into()),
+        5 => Some(d5::FIVE.into()),
+        6 => Some(d6::SIX.into()),
+        7 => Some(d7::SEVEN.into()),
+        8 => Some(d8::EIGHT.into()),
+        _ => None,
+    }
+}
 
-    Some(out)
+/// The premade optimized layout of [`diagram(n)`](diagram), if there is one.
+#[must_use]
+pub fn layout_optimized(n: usize) -> Option<Layout> {
+    match n {
+        2 => Some(d2::LAYOUT_OPTIMIZED_TWO.into()),
+        3 => Some(d3::LAYOUT_OPTIMIZED_THREE.into()),
+        4 => Some(d4::LAYOUT_OPTIMIZED_FOUR.into()),
+        5 => Some(d5::LAYOUT_OPTIMIZED_FIVE.into()),
+        6 => Some(d6::LAYOUT_OPTIMIZED_SIX.into()),
+        7 => Some(d7::LAYOUT_OPTIMIZED_SEVEN.into()),
+        8 => Some(d8::LAYOUT_OPTIMIZED_EIGHT.into()),
+        _ => None,
+    }
 }
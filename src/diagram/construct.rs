@@ -0,0 +1,121 @@
+use super::{Diagram, DiagramError, MAX_SETS, SetMask};
+use crate::{matrix::Matrix, polyomino::Polyomino};
+
+/// Construct a minimum area Venn diagram for `n` sets from 2x2 blocks.
+///
+/// This isn't the construction of [\[2006\]](crate#ref-2006), so the
+/// diagrams differ from the premade ones in [`constants`](crate::constants).
+/// Each step adds two sets, since splitting each cell in two for a single new
+/// set can't keep that set connected. Each cell of the diagram for `n - 2`
+/// sets is replaced by a 2x2 block containing the subsets `S`, `S+a`, `S+b`
+/// and `S+ab`, where `a` and `b` are the new sets. The blocks are oriented so
+/// that `a` and `b` become combs which reach every block.
+///
+/// The diagram fills a rectangle of size `2^ceil(n/2)` by `2^floor(n/2)`,
+/// except for a single empty cell on its border. Every polyomino is connected
+/// and without holes.
+///
+/// Every polyomino has a byte for each of the `2^n` cells, so the diagram
+/// takes `n * 2^n` bytes, which is about 20 MB for 20 sets and 1.7 GB for
+/// [`MAX_SETS`].
+///
+/// Returns an error if `n` is zero or larger than [`MAX_SETS`].
+///
+/// ```
+/// use venn_diagrams::diagram::construct_blocks;
+///
+/// let diagram = construct_blocks(5).unwrap();
+/// assert_eq!((diagram.width(), diagram.height()), (8, 4));
+///
+/// let validation = diagram.validate();
+/// assert!(validation.is_valid());
+/// assert!(validation.minimum_area);
+/// ```
+pub fn construct_blocks(n: usize) -> Result<Diagram, DiagramError> {
+    if n == 0 {
+        return Err(DiagramError::NoSets);
+    }
+    if n > MAX_SETS {
+        return Err(DiagramError::TooManySets { n });
+    }
+
+    // Each cell is the bitmask of the subset it contains
+    let (mut width, mut height, mut cells, mut k) = if n % 2 == 1 {
+        let mut cells = Matrix::new(2, 1, 0);
+        cells[(0, 0)] = 0b1;
+        (2, 1, cells, 1)
+    } else {
+        let mut cells = Matrix::new(2, 2, 0);
+        cells[(0, 0)] = 0b01;
+        cells[(1, 0)] = 0b11;
+        cells[(1, 1)] = 0b10;
+        (2, 2, cells, 2)
+    };
+
+    while k != n {
+        let a: SetMask = 1 << k;
+        let b: SetMask = 1 << (k + 1);
+        let mut next = Matrix::new(2 * width, 2 * height, 0);
+        for y in 0..height {
+            for x in 0..width {
+                let s = cells[(x, y)];
+                // Positions of `S`, `S+a`, `S+b` and `S+ab` in the block. Set `a` is a
+                // comb with a spine along the left side of the diagram, and `b` is a
+                // comb with a spine along the top.
+                let positions = match (x, y) {
+                    (0, 0) => [(0, 0), (1, 0), (0, 1), (1, 1)],
+                    (_, 0) => [(1, 0), (0, 0), (1, 1), (0, 1)],
+                    (0, _) => [(0, 1), (1, 1), (0, 0), (1, 0)],
+                    (_, _) => [(1, 1), (1, 0), (0, 1), (0, 0)],
+                };
+                for ((dx, dy), mask) in positions.into_iter().zip([s, s | a, s | b, s | a | b]) {
+                    next[(2 * x + dx, 2 * y + dy)] = mask;
+                }
+            }
+        }
+        width *= 2;
+        height *= 2;
+        cells = next;
+        k += 2;
+    }
+
+    let polyominos = (0..n)
+        .map(|i| {
+            let mut poly = Polyomino::empty(width, height);
+            for y in 0..height {
+                for x in 0..width {
+                    poly[(x, y)] = cells[(x, y)] & (1 << i) != 0;
+                }
+            }
+            poly
+        })
+        .collect();
+
+    Diagram::new(polyominos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        for n in 1..=10 {
+            let diagram = construct_blocks(n).unwrap();
+            assert_eq!(diagram.n(), n);
+            assert_eq!(diagram.width() * diagram.height(), 1 << n);
+            let validation = diagram.validate();
+            assert!(validation.is_valid());
+            assert!(validation.minimum_area);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(construct_blocks(0), Err(DiagramError::NoSets));
+        assert_eq!(
+            construct_blocks(MAX_SETS + 1),
+            Err(DiagramError::TooManySets { n: MAX_SETS + 1 })
+        );
+    }
+}
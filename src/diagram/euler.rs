@@ -0,0 +1,95 @@
+use super::{Diagram, DiagramError, SetMask};
+use crate::polyomino::Polyomino;
+
+impl Diagram {
+    /// Create an [Euler diagram][wp-euler] by removing every cell which isn't
+    /// one of the given subsets, and then removing empty rows and columns at
+    /// the border.
+    ///
+    /// Returns an error if a subset isn't part of any cell, or if a polyomino
+    /// becomes empty or disconnected.
+    ///
+    /// ```
+    /// use venn_diagrams::{constants::d3::THREE, diagram::Diagram};
+    ///
+    /// // Every subset except `A`
+    /// let subsets = [0b011, 0b111, 0b010, 0b101, 0b110, 0b100];
+    /// let diagram = Diagram::from(THREE).restrict(&subsets).unwrap();
+    /// assert!(diagram.validate_euler(&subsets).is_valid());
+    /// ```
+    ///
+    /// [wp-euler]: https://en.wikipedia.org/wiki/Euler_diagram
+    pub fn restrict(&self, subsets: &[SetMask]) -> Result<Self, DiagramError> {
+        let mut keep = vec![false; self.width() * self.height()];
+        for &subset in subsets {
+            if subset == 0 {
+                continue;
+            }
+            let mut found = false;
+            for y in 0..self.height() {
+                for x in 0..self.width() {
+                    if self.mask_at(x, y) == subset {
+                        keep[y * self.width() + x] = true;
+                        found = true;
+                    }
+                }
+            }
+            if !found {
+                return Err(DiagramError::MissingSubset { subset });
+            }
+        }
+
+        // The bounding box of the cells we keep
+        let kept = |x: usize, y: usize| keep[y * self.width() + x];
+        let columns: Vec<usize> =
+            (0..self.width()).filter(|x| (0..self.height()).any(|y| kept(*x, y))).collect();
+        let rows: Vec<usize> =
+            (0..self.height()).filter(|y| (0..self.width()).any(|x| kept(x, *y))).collect();
+        let (x_min, x_max) =
+            (columns.first().copied().unwrap_or(0), columns.last().map_or(0, |x| x + 1));
+        let (y_min, y_max) = (rows.first().copied().unwrap_or(0), rows.last().map_or(0, |y| y + 1));
+
+        let mut polyominos = Vec::with_capacity(self.n());
+        for (set, poly) in self.polyominos.iter().enumerate() {
+            let mut out = Polyomino::empty(x_max - x_min, y_max - y_min);
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    out[(x - x_min, y - y_min)] = kept(x, y) && poly[(x, y)];
+                }
+            }
+            if !out.is_empty() && !out.is_connected() {
+                return Err(DiagramError::Disconnected { set });
+            }
+            polyominos.push(out);
+        }
+
+        Self::new(polyominos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::d3::THREE;
+
+    #[test]
+    fn restrict() {
+        let diagram: Diagram = THREE.into();
+        let subsets = [0b011, 0b111, 0b010, 0b101, 0b110, 0b100];
+        let restricted = diagram.restrict(&subsets).unwrap();
+        assert_eq!(restricted.width(), 4);
+        assert_eq!(restricted.height(), 2);
+        let validation = restricted.validate_euler(&subsets);
+        assert!(validation.is_valid());
+        assert!(validation.minimum_area);
+
+        // Removing `AB` splits `A` in two
+        let subsets = [0b001, 0b111, 0b010, 0b101, 0b110, 0b100];
+        assert_eq!(diagram.restrict(&subsets), Err(DiagramError::Disconnected { set: 0 }));
+
+        assert_eq!(
+            diagram.restrict(&[0b1000]),
+            Err(DiagramError::MissingSubset { subset: 0b1000 })
+        );
+    }
+}
@@ -0,0 +1,569 @@
+//! ## Venn diagrams
+//!
+//! A Venn diagram consists of multiple polyominos (see the
+//! [polyomino][crate::polyomino] module).
+//!
+//! There are two versions:
+//! - [`Diagram`], dynamic version
+//! - [`DiagramConst`], static version
+
+mod construct;
+mod euler;
+mod permute;
+mod regions;
+mod search;
+mod transform;
+mod validate;
+
+use std::{error::Error, fmt};
+
+pub use construct::construct_blocks;
+#[cfg(feature = "optimize")]
+pub use search::{SearchConfig, Symmetry, search};
+pub use validate::{Validation, WrongSize};
+
+use crate::polyomino::{ConstPolyomino, Polyomino};
+
+/// A statically allocated Venn diagram.
+///
+/// It has three generic parameters:
+/// - `N`, number of polyominos
+/// - `X`, maximum width of the Venn diagram
+/// - `Y`, maximum height of the Venn diagram
+#[derive(Debug, Clone)]
+pub struct DiagramConst<const N: usize, const X: usize, const Y: usize> {
+    pub(crate) polyominos: [ConstPolyomino<X, Y>; N],
+}
+
+impl<const N: usize, const X: usize, const Y: usize> DiagramConst<N, X, Y> {
+    /// Create a new Venn diagram from an array of polyominos.
+    #[must_use]
+    pub const fn new(polyominos: [ConstPolyomino<X, Y>; N]) -> Self {
+        Self { polyominos }
+    }
+
+    /// Check if the Venn diagram consists of all subsets (except the empty
+    /// subset)
+    #[must_use]
+    pub const fn complete(&self) -> bool {
+        // We check that each group is the right size
+        let count_goal: u64 = 1 << (N - 1);
+        let mut i = 0;
+        while i != N {
+            let mut count = 0;
+            let part = &self.polyominos[i];
+            let mut x = 0;
+            while x != X {
+                let mut y = 0;
+                while y != Y {
+                    if part.values[y][x] {
+                        count += 1;
+                    }
+                    y += 1;
+                }
+                x += 1;
+            }
+
+            if count != count_goal {
+                return false;
+            }
+            i += 1;
+        }
+
+        // Each cell is different
+        let mut x1 = 0;
+        while x1 != X {
+            let mut y1 = 0;
+            while y1 != Y {
+                let mut x2 = 0;
+                while x2 != X {
+                    let mut y2 = 0;
+                    while y2 != Y {
+                        let eq_coord = x1 == x2 && y1 == y2;
+                        let all_false_1 = self.empty_at(x1, y1);
+                        let all_false_2 = self.empty_at(x2, y2);
+                        if !eq_coord
+                            && !all_false_1
+                            && !all_false_2
+                            && !self.different_at(x1, y1, x2, y2)
+                        {
+                            return false;
+                        }
+                        y2 += 1;
+                    }
+                    x2 += 1;
+                }
+                y1 += 1;
+            }
+            x1 += 1;
+        }
+
+        true
+    }
+
+    /// Create a Venn diagram from an array of arrays of strings, where each
+    /// string is a sequence of `0` and `1`.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::DiagramConst;
+    ///
+    /// let s = [
+    ///     [
+    ///         "1000",
+    ///         "1100",
+    ///     ],
+    ///     [
+    ///         "1110",
+    ///         "0100",
+    ///     ],
+    ///     [
+    ///         "0110",
+    ///         "0011",
+    ///     ],
+    /// ];
+    /// let venn_diagram: DiagramConst<3, 4, 2> =
+    ///     DiagramConst::from_binary_str(s);
+    /// ```
+    #[must_use]
+    pub const fn from_binary_str(grids: [[&str; Y]; N]) -> Self {
+        let mut out = [ConstPolyomino::empty(); N];
+        let mut i = 0;
+        while i != N {
+            out[i] = ConstPolyomino::from_binary_str(grids[i]);
+            i += 1;
+        }
+
+        Self::new(out)
+    }
+
+    /// Create a Venn diagram from an array of arrays of strings, where each
+    /// string is a sequence of letters that are part of that intersection.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::DiagramConst;
+    ///
+    /// let s = [
+    ///     ["AB", "BC", "BC", ""],
+    ///     ["A",  "AB", "C",  "C"],
+    /// ];
+    /// let venn_diagram: DiagramConst<3, 4, 2> = DiagramConst::from_letters(s);
+    /// ```
+    ///
+    /// Panics if the input is invalid, i.e. if a character is not an uppercase
+    /// letter, or if a letter refers to a set larger than `N`.
+    #[must_use]
+    pub const fn from_letters(boxes: [[&str; X]; Y]) -> Self {
+        let mut out = [ConstPolyomino::empty(); N];
+        let mut y = 0;
+        while y != Y {
+            let mut x = 0;
+            while x != X {
+                let s = boxes[y][x].as_bytes();
+                let mut c_i = 0;
+                while c_i != s.len() {
+                    let c = s[c_i];
+                    if !c.is_ascii_uppercase() {
+                        panic!("Invalid character");
+                    }
+
+                    let p = (c - b'A') as usize;
+                    if p >= N {
+                        panic!("Set out of range");
+                    }
+                    out[p].values[y][x] = true;
+                    c_i += 1;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        Self::new(out)
+    }
+
+    const fn empty_at(&self, x: usize, y: usize) -> bool {
+        let mut i = 0;
+        while i != N {
+            let p2 = self.polyominos[i].values[y][x];
+            if p2 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    const fn different_at(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> bool {
+        let mut i = 0;
+        while i != N {
+            let p1 = self.polyominos[i].values[y1][x1];
+            let p2 = self.polyominos[i].values[y2][x2];
+            if p1 != p2 {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+impl<const N: usize, const X: usize, const Y: usize> From<DiagramConst<N, X, Y>> for Diagram {
+    fn from(value: DiagramConst<N, X, Y>) -> Self {
+        Self {
+            width: X,
+            height: Y,
+            polyominos: value.polyominos.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A dynamically allocated Venn diagram.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagram {
+    width: usize,
+    height: usize,
+    pub(crate) polyominos: Vec<Polyomino>,
+}
+
+/// A subset of the sets in a Venn diagram, as a bitmask where bit `i` is set
+/// if set `i` is part of the subset.
+pub type SetMask = u32;
+
+/// Maximum number of sets in a [`Diagram`], one for each letter `A` to `Z`.
+pub const MAX_SETS: usize = 26;
+
+/// Error returned when a [`Diagram`] can't be created from the given input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagramError {
+    /// A character which is not allowed in the input format. `set` is the
+    /// index of the grid the character was found in, if the input consists of
+    /// multiple grids.
+    InvalidCharacter {
+        /// Index of the grid, if any.
+        set: Option<usize>,
+        /// Column of the character.
+        x: usize,
+        /// Row of the character.
+        y: usize,
+        /// The invalid character.
+        character: char,
+    },
+
+    /// A row with a different width than the first row.
+    RaggedRow {
+        /// Index of the grid, if any.
+        set: Option<usize>,
+        /// Index of the row.
+        y: usize,
+        /// Width of the row.
+        width: usize,
+        /// Width of the first row.
+        expected: usize,
+    },
+
+    /// A polyomino with a different size than the first polyomino.
+    MismatchedSize {
+        /// Index of the polyomino.
+        set: usize,
+        /// Width of the polyomino.
+        width: usize,
+        /// Height of the polyomino.
+        height: usize,
+    },
+
+    /// A polyomino which consists of multiple components.
+    Disconnected {
+        /// Index of the polyomino.
+        set: usize,
+    },
+
+    /// A subset which isn't part of any cell.
+    MissingSubset {
+        /// Bitmask of the subset.
+        subset: SetMask,
+    },
+
+    /// A set which doesn't contain any cell.
+    EmptySet {
+        /// Index of the set.
+        set: usize,
+    },
+
+    /// More sets than [`MAX_SETS`].
+    TooManySets {
+        /// Number of sets in the input.
+        n: usize,
+    },
+
+    /// The input doesn't contain any set.
+    NoSets,
+
+    /// A size with more cells than fit in a `usize`.
+    TooLarge {
+        /// Width of the diagram.
+        width: usize,
+        /// Height of the diagram.
+        height: usize,
+    },
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter { set: Some(set), x, y, character } => {
+                write!(f, "invalid character {character:?} at ({x}, {y}) in grid {set}")
+            }
+            Self::InvalidCharacter { set: None, x, y, character } => {
+                write!(f, "invalid character {character:?} at ({x}, {y})")
+            }
+            Self::RaggedRow { set: Some(set), y, width, expected } => {
+                write!(f, "row {y} in grid {set} has width {width}, expected {expected}")
+            }
+            Self::RaggedRow { set: None, y, width, expected } => {
+                write!(f, "row {y} has width {width}, expected {expected}")
+            }
+            Self::MismatchedSize { set, width, height } => {
+                write!(f, "polyomino {set} has mismatched size {width}x{height}")
+            }
+            Self::Disconnected { set } => write!(f, "polyomino {set} is disconnected"),
+            Self::MissingSubset { subset } => {
+                write!(f, "subset {subset:#b} isn't part of any cell")
+            }
+            Self::EmptySet { set } => write!(f, "set {set} doesn't contain any cell"),
+            Self::TooManySets { n } => {
+                write!(f, "diagram has {n} sets, but at most {MAX_SETS} are supported")
+            }
+            Self::NoSets => write!(f, "diagram doesn't contain any set"),
+            Self::TooLarge { width, height } => {
+                write!(f, "diagram of size {width}x{height} has too many cells")
+            }
+        }
+    }
+}
+
+impl Error for DiagramError {}
+
+impl Diagram {
+    /// Create a Venn diagram from a list of polyominos, which must all have the
+    /// same size.
+    ///
+    /// Returns an error if the sizes differ, if a polyomino is empty or if
+    /// there are too few or too many polyominos.
+    pub fn new(polyominos: Vec<Polyomino>) -> Result<Self, DiagramError> {
+        let Some(first) = polyominos.first() else {
+            return Err(DiagramError::NoSets);
+        };
+        if polyominos.len() > MAX_SETS {
+            return Err(DiagramError::TooManySets { n: polyominos.len() });
+        }
+        let (width, height) = (first.width(), first.height());
+        for (set, poly) in polyominos.iter().enumerate() {
+            if poly.width() != width || poly.height() != height {
+                return Err(DiagramError::MismatchedSize {
+                    set,
+                    width: poly.width(),
+                    height: poly.height(),
+                });
+            }
+            if poly.is_empty() {
+                return Err(DiagramError::EmptySet { set });
+            }
+        }
+
+        Ok(Self { width, height, polyominos })
+    }
+
+    /// Create a Venn diagram from a string, where each line is a row of cells
+    /// separated by whitespace. Each cell is a sequence of letters that are
+    /// part of that intersection, or `.` if the cell is empty. The number of
+    /// sets is decided by the largest letter.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let s = "
+    ///     AB BC BC .
+    ///     A  AB C  C
+    /// ";
+    /// let venn_diagram = Diagram::from_letters(s).unwrap();
+    /// assert_eq!(venn_diagram.n(), 3);
+    /// ```
+    pub fn from_letters(s: &str) -> Result<Self, DiagramError> {
+        let rows: Vec<Vec<&str>> = s
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<&str>>())
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        let mut cells: Vec<SetMask> = Vec::with_capacity(width * height);
+        let mut n = 0;
+        for (y, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(DiagramError::RaggedRow {
+                    set: None,
+                    y,
+                    width: row.len(),
+                    expected: width,
+                });
+            }
+            for (x, cell) in row.iter().enumerate() {
+                let mut sets = 0;
+                if *cell != "." {
+                    for character in cell.chars() {
+                        if !character.is_ascii_uppercase() {
+                            return Err(DiagramError::InvalidCharacter {
+                                set: None,
+                                x,
+                                y,
+                                character,
+                            });
+                        }
+                        let i = character as usize - 'A' as usize;
+                        n = n.max(i + 1);
+                        sets |= 1 << i;
+                    }
+                }
+                cells.push(sets);
+            }
+        }
+
+        let polyominos = (0..n)
+            .map(|i| {
+                let mut poly = Polyomino::empty(width, height);
+                for (j, sets) in cells.iter().enumerate() {
+                    poly[(j % width, j / width)] = sets & (1 << i) != 0;
+                }
+                poly
+            })
+            .collect();
+
+        Self::new(polyominos)
+    }
+
+    /// Create a Venn diagram from a list of grids, one for each polyomino,
+    /// where each line is a sequence of `0` and `1`.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let grids = [
+    ///     "1000
+    ///      1100",
+    ///     "1110
+    ///      0100",
+    ///     "0110
+    ///      0011",
+    /// ];
+    /// let venn_diagram = Diagram::from_binary_grids(&grids).unwrap();
+    /// assert_eq!(venn_diagram.n(), 3);
+    /// ```
+    pub fn from_binary_grids<S: AsRef<str>>(grids: &[S]) -> Result<Self, DiagramError> {
+        let polyominos = grids
+            .iter()
+            .enumerate()
+            .map(|(set, grid)| {
+                Polyomino::from_binary_str(grid.as_ref()).map_err(|err| match err {
+                    DiagramError::InvalidCharacter { x, y, character, .. } => {
+                        DiagramError::InvalidCharacter { set: Some(set), x, y, character }
+                    }
+                    DiagramError::RaggedRow { y, width, expected, .. } => {
+                        DiagramError::RaggedRow { set: Some(set), y, width, expected }
+                    }
+                    err => err,
+                })
+            })
+            .collect::<Result<Vec<Polyomino>, DiagramError>>()?;
+
+        Self::new(polyominos)
+    }
+
+    /// Maximum width of the Venn diagram.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Maximum height of the Venn diagram.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of polyominos.
+    #[must_use]
+    pub fn n(&self) -> usize {
+        self.polyominos.len()
+    }
+
+    // Bitmask of which sets contain cell (x, y)
+    fn mask_at(&self, x: usize, y: usize) -> SetMask {
+        let mut mask = 0;
+        for (i, poly) in self.polyominos.iter().enumerate() {
+            if poly[(x, y)] {
+                mask |= 1 << i;
+            }
+        }
+        mask
+    }
+}
+
+impl Clone for Diagram {
+    fn clone(&self) -> Self {
+        Self { width: self.width, height: self.height, polyominos: self.polyominos.clone() }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.width = source.width;
+        self.height = source.height;
+        self.polyominos.clone_from(&source.polyominos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::d3::THREE;
+
+    #[test]
+    fn from_letters() {
+        let s = "
+            A AB ABC B  .
+            . .  AC  BC C
+        ";
+        let diagram = Diagram::from_letters(s).unwrap();
+        assert_eq!(diagram, THREE.into());
+    }
+
+    #[test]
+    fn from_letters_errors() {
+        let err = Diagram::from_letters("A Ab").unwrap_err();
+        assert_eq!(err, DiagramError::InvalidCharacter { set: None, x: 1, y: 0, character: 'b' });
+
+        let err = Diagram::from_letters("A B\nA B AB").unwrap_err();
+        assert_eq!(err, DiagramError::RaggedRow { set: None, y: 1, width: 3, expected: 2 });
+
+        assert_eq!(Diagram::from_letters("A C").unwrap_err(), DiagramError::EmptySet { set: 1 });
+        assert_eq!(Diagram::from_letters(". .").unwrap_err(), DiagramError::NoSets);
+    }
+
+    #[test]
+    fn from_binary_grids() {
+        let grids = ["11100\n00100", "01110\n00010", "00100\n00111"];
+        let diagram = Diagram::from_binary_grids(&grids).unwrap();
+        assert_eq!(diagram, THREE.into());
+
+        let err = Diagram::from_binary_grids(&["11100\n00100", "0111\n0000"]).unwrap_err();
+        assert_eq!(err, DiagramError::MismatchedSize { set: 1, width: 4, height: 2 });
+
+        let err = Diagram::from_binary_grids(&["11100\n00100", "01110\n0000"]).unwrap_err();
+        assert_eq!(err, DiagramError::RaggedRow { set: Some(1), y: 1, width: 4, expected: 5 });
+
+        let err = Diagram::from_binary_grids(&["11100\n00100", "01120\n00000"]).unwrap_err();
+        assert_eq!(
+            err,
+            DiagramError::InvalidCharacter { set: Some(1), x: 3, y: 0, character: '2' }
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use super::{Diagram, SetMask};
+use crate::coalition::Game;
+
+// Up to this many sets every permutation is tried, otherwise we use a local
+// search.
+const EXHAUSTIVE_MAX_SETS: usize = 8;
+
+/// Returns `true` if `permutation` contains every index in `0..n` once.
+pub(crate) fn is_permutation(permutation: &[usize], n: usize) -> bool {
+    let mut seen = vec![false; n];
+    permutation.len() == n
+        && permutation.iter().all(|&i| i < n && !std::mem::replace(&mut seen[i], true))
+}
+
+impl Diagram {
+    /// Reorder the sets, so that set `i` of the new diagram is set
+    /// `permutation[i]` of this diagram.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutation` isn't a permutation of `0..n`.
+    #[must_use]
+    pub fn permute_sets(&self, permutation: &[usize]) -> Self {
+        assert!(is_permutation(permutation, self.n()), "Invalid permutation");
+        let polyominos = permutation.iter().map(|&i| self.polyominos[i].clone()).collect();
+        Self { width: self.width, height: self.height, polyominos }
+    }
+
+    /// Find the permutation for [`Diagram::permute_sets`] which best clusters
+    /// the winning coalitions of `game`, where set `i` of the new diagram has
+    /// the weight `game.values()[i]`. To match the circles of a diagram, use
+    /// the game of the band of [`DiagramConfig::thresholds`] with the largest
+    /// threshold, which is a majority of at least half of the total by
+    /// default.
+    ///
+    /// The permutation minimizes the length of the border between cells with
+    /// and without a winning coalition, where the area outside of the diagram
+    /// doesn't win. Every permutation is tried for up to 8 sets, otherwise
+    /// sets are swapped as long as it improves the result.
+    ///
+    /// ```
+    /// use venn_diagrams::{coalition::Game, constants::d4::FOUR, diagram::Diagram};
+    ///
+    /// let diagram = Diagram::from(FOUR);
+    /// let game = Game::new(vec![0.4, 0.3, 0.2, 0.1], 0.5);
+    /// let permutation = diagram.best_permutation(&game);
+    /// let diagram = diagram.permute_sets(&permutation);
+    /// ```
+    ///
+    /// [`DiagramConfig::thresholds`]: crate::svg::DiagramConfig::thresholds
+    ///
+    /// # Panics
+    ///
+    /// Panics if `game` doesn't have one weight for each set.
+    #[must_use]
+    pub fn best_permutation(&self, game: &Game) -> Vec<usize> {
+        let n = self.n();
+        assert_eq!(game.n(), n, "Wrong number of values");
+
+        // Number of times each pair of subsets are neighbours
+        let mut neighbours: HashMap<(SetMask, SetMask), usize> = HashMap::new();
+        let mask_at = |x: usize, y: usize| {
+            if x < self.width && y < self.height { self.mask_at(x, y) } else { 0 }
+        };
+        for y in 0..=self.height {
+            for x in 0..=self.width {
+                let mask = mask_at(x, y);
+                if x != 0 {
+                    *neighbours.entry((mask_at(x - 1, y), mask)).or_default() += 1;
+                }
+                if y != 0 {
+                    *neighbours.entry((mask_at(x, y - 1), mask)).or_default() += 1;
+                }
+            }
+        }
+        neighbours.retain(|(a, b), _| a != b);
+
+        let border = |permutation: &[usize]| -> usize {
+            // Whether a subset of this diagram wins, where set `permutation[i]`
+            // becomes set `i`
+            let wins = |mask: SetMask| {
+                let permuted = (0..n)
+                    .filter(|&i| mask & (1 << permutation[i]) != 0)
+                    .fold(0, |acc, i| acc | (1 << i));
+                mask != 0 && game.is_winning(permuted)
+            };
+            neighbours.iter().filter(|((a, b), _)| wins(*a) != wins(*b)).map(|(_, c)| c).sum()
+        };
+
+        if n <= EXHAUSTIVE_MAX_SETS {
+            return (0..n).permutations(n).min_by_key(|p| border(p)).unwrap();
+        }
+
+        let mut best: Vec<usize> = (0..n).collect();
+        let mut best_border = border(&best);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for (i, j) in (0..n).tuple_combinations() {
+                best.swap(i, j);
+                let new_border = border(&best);
+                if new_border < best_border {
+                    best_border = new_border;
+                    improved = true;
+                } else {
+                    best.swap(i, j);
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_permutation;
+    use crate::{coalition::Game, constants::d4::FOUR, diagram::Diagram};
+
+    #[test]
+    fn permute() {
+        let diagram = Diagram::from_letters("A AB B\nAC ABC BC\n. C .").unwrap();
+        let permuted = Diagram::from_letters("C BC B\nAC ABC AB\n. A .").unwrap();
+        assert_eq!(diagram.permute_sets(&[2, 1, 0]), permuted);
+        assert_eq!(diagram.permute_sets(&[0, 1, 2]), diagram);
+
+        assert!(is_permutation(&[1, 2, 0], 3));
+        assert!(!is_permutation(&[1, 1, 0], 3));
+        assert!(!is_permutation(&[1, 0], 3));
+        assert!(!is_permutation(&[1, 3, 0], 3));
+    }
+
+    #[test]
+    fn best_permutation() {
+        // Every coalition with set 0 has a majority, so it should be one of the
+        // smaller polyominos at the ends, with a shorter border.
+        let diagram = Diagram::from_letters("A AB B BC C").unwrap();
+        let permutation = diagram.best_permutation(&Game::new(vec![0.6, 0.2, 0.2], 0.5));
+        assert_ne!(permutation[0], 1);
+        assert_eq!(diagram.permute_sets(&permutation).polyominos[0].count(), 2);
+
+        // With a threshold of 2/3 only coalitions with both set 0 and set 1
+        // win, so they shouldn't share a cell
+        let permutation = diagram.best_permutation(&Game::new(vec![0.4, 0.35, 0.25], 2.0 / 3.0));
+        let permuted = diagram.permute_sets(&permutation);
+        assert!((0..5).all(|x| permuted.subset_at(x, 0).unwrap() & 0b011 != 0b011));
+
+        let diagram = Diagram::from(FOUR);
+        let permutation = diagram.best_permutation(&Game::new(vec![0.4, 0.3, 0.2, 0.1], 0.5));
+        assert!(is_permutation(&permutation, 4));
+    }
+}
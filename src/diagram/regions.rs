@@ -0,0 +1,69 @@
+use super::{Diagram, SetMask};
+
+impl Diagram {
+    /// The subset of sets which contain the cell at (`x`, `y`). Returns `None`
+    /// if the cell is outside of the diagram or isn't part of any set.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let diagram = Diagram::from_letters("A AB B").unwrap();
+    /// assert_eq!(diagram.subset_at(1, 0), Some(0b11));
+    /// assert_eq!(diagram.subset_at(3, 0), None);
+    /// ```
+    #[must_use]
+    pub fn subset_at(&self, x: usize, y: usize) -> Option<SetMask> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.mask_at(x, y)).filter(|mask| *mask != 0)
+    }
+
+    /// The first cell, going row by row, which contains exactly the sets in
+    /// `subset`. Returns `None` if there is no such cell, or if `subset` is
+    /// empty.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let diagram = Diagram::from_letters("A AB B").unwrap();
+    /// assert_eq!(diagram.cell_of(0b10), Some((2, 0)));
+    /// assert_eq!(diagram.cell_of(0b100), None);
+    /// ```
+    #[must_use]
+    pub fn cell_of(&self, subset: SetMask) -> Option<(usize, usize)> {
+        self.regions().find(|(mask, _)| *mask == subset).map(|(_, cell)| cell)
+    }
+
+    /// Iterate over every cell which is part of at least one set, row by row,
+    /// together with the subset of sets which contain it.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let diagram = Diagram::from_letters("A AB\n. B").unwrap();
+    /// let regions: Vec<_> = diagram.regions().collect();
+    /// assert_eq!(regions, vec![(0b01, (0, 0)), (0b11, (1, 0)), (0b10, (1, 1))]);
+    /// ```
+    pub fn regions(&self) -> impl Iterator<Item = (SetMask, (usize, usize))> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| self.subset_at(x, y).map(|mask| (mask, (x, y))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constants::d5::FIVE, diagram::Diagram};
+
+    #[test]
+    fn regions() {
+        let diagram: Diagram = FIVE.into();
+        assert_eq!(diagram.regions().count(), 31);
+        for (mask, (x, y)) in diagram.regions() {
+            assert_eq!(diagram.cell_of(mask), Some((x, y)));
+            assert_eq!(diagram.subset_at(x, y), Some(mask));
+        }
+        assert_eq!(diagram.cell_of(0), None);
+    }
+}
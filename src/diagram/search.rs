@@ -0,0 +1,259 @@
+#![cfg(feature = "optimize")]
+
+use z3::{
+    SatResult, Solver,
+    ast::{Bool, Int},
+};
+
+use super::{Diagram, DiagramError, MAX_SETS};
+use crate::polyomino::Polyomino;
+
+/// Rotational symmetry of a Venn diagram found by [`search`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Rotating the diagram 180° maps polyomino `i` to polyomino
+    /// `(i + n/2) % n`. Requires an even number of sets.
+    Rotate180,
+
+    /// Rotating the diagram 90° clockwise maps polyomino `i` to polyomino
+    /// `(i + n/4) % n`. Requires the number of sets to be divisible by four,
+    /// and a square bounding box.
+    Rotate90,
+}
+
+/// Configuration of [`search`].
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// Number of sets.
+    pub n: usize,
+
+    /// Maximum width of the Venn diagram.
+    pub width: usize,
+
+    /// Maximum height of the Venn diagram.
+    pub height: usize,
+
+    /// Rotational symmetry of the Venn diagram, if any.
+    pub symmetry: Option<Symmetry>,
+
+    /// If every row and column of each polyomino should be contiguous.
+    pub convex: bool,
+
+    /// Maximum number of Venn diagrams to find.
+    pub limit: usize,
+}
+
+impl SearchConfig {
+    /// Search for a single Venn diagram of `n` sets, which fits in a box of
+    /// size `width` by `height`, without any other constraints.
+    #[must_use]
+    pub fn new(n: usize, width: usize, height: usize) -> Self {
+        Self { n, width, height, symmetry: None, convex: false, limit: 1 }
+    }
+}
+
+// Both are true or both are false
+fn equal(a: &Bool, b: &Bool) -> Bool {
+    Bool::or(&[Bool::and(&[a.clone(), b.clone()]), Bool::and(&[a.not(), b.not()])])
+}
+
+/// Search for minimum area Venn diagrams using the z3 SMT solver, where each
+/// non-empty subset is part of exactly one cell and each polyomino is
+/// connected.
+///
+/// Returns at most `config.limit` different diagrams, or none if there are no
+/// diagrams which satisfy the configuration. Returns an error if `config.n` is
+/// zero or larger than [`MAX_SETS`], or if the box has more cells than fit in
+/// a `usize`.
+///
+/// ```
+/// use venn_diagrams::diagram::{SearchConfig, Symmetry, search};
+///
+/// let mut config = SearchConfig::new(2, 3, 1);
+/// config.symmetry = Some(Symmetry::Rotate180);
+/// let diagrams = search(&config).unwrap();
+/// assert_eq!(diagrams.len(), 1);
+/// assert!(diagrams[0].validate().is_valid());
+/// ```
+pub fn search(config: &SearchConfig) -> Result<Vec<Diagram>, DiagramError> {
+    let SearchConfig { n, width, height, symmetry, convex, limit } = *config;
+    if n == 0 {
+        return Err(DiagramError::NoSets);
+    }
+    if n > MAX_SETS {
+        return Err(DiagramError::TooManySets { n });
+    }
+    let cells = width.checked_mul(height).ok_or(DiagramError::TooLarge { width, height })?;
+    if cells < (1 << n) - 1 {
+        return Ok(Vec::new());
+    }
+
+    let solver = Solver::new();
+    let index = |x: usize, y: usize| y * width + x;
+
+    // `cell_variables[i][index(x, y)]` is true if polyomino `i` contains (x, y)
+    let cell_variables: Vec<Vec<Bool>> =
+        (0..n).map(|_| (0..cells).map(|_| Bool::fresh_const("cell")).collect()).collect();
+    let contains = |i: usize, x: usize, y: usize| &cell_variables[i][index(x, y)];
+    let non_empty = |x: usize, y: usize| -> Bool {
+        let parts: Vec<Bool> = (0..n).map(|i| contains(i, x, y).clone()).collect();
+        Bool::or(&parts)
+    };
+
+    // Each non-empty subset is part of at least one cell
+    for subset in 1..(1_usize << n) {
+        let mut parts: Vec<Bool> = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pattern: Vec<Bool> = (0..n)
+                    .map(|i| {
+                        let b = contains(i, x, y);
+                        if subset & (1 << i) != 0 { b.clone() } else { b.not() }
+                    })
+                    .collect();
+                parts.push(Bool::and(&pattern));
+            }
+        }
+        solver.assert(&Bool::or(&parts));
+    }
+
+    // ...and at most one cell, because each non-empty cell is different
+    for c1 in 0..cells {
+        for c2 in 0..c1 {
+            let (x1, y1) = (c1 % width, c1 / width);
+            let (x2, y2) = (c2 % width, c2 / width);
+            let mut parts: Vec<Bool> = vec![non_empty(x1, y1).not(), non_empty(x2, y2).not()];
+            for i in 0..n {
+                parts.push(equal(contains(i, x1, y1), contains(i, x2, y2)).not());
+            }
+            solver.assert(&Bool::or(&parts));
+        }
+    }
+
+    // Each polyomino is connected: every cell, except the cell containing every
+    // set, has a neighbour in the same polyomino which is closer to that cell.
+    let neighbours = |x: usize, y: usize| {
+        let mut out = Vec::with_capacity(4);
+        if x != 0 {
+            out.push((x - 1, y));
+        }
+        if y != 0 {
+            out.push((x, y - 1));
+        }
+        if x + 1 != width {
+            out.push((x + 1, y));
+        }
+        if y + 1 != height {
+            out.push((x, y + 1));
+        }
+        out
+    };
+    for i in 0..n {
+        let distances: Vec<Int> = (0..cells).map(|_| Int::fresh_const("distance")).collect();
+        for y in 0..height {
+            for x in 0..width {
+                let distance = &distances[index(x, y)];
+                solver.assert(&distance.ge(0));
+
+                let full: Vec<Bool> = (0..n).map(|j| contains(j, x, y).clone()).collect();
+                let mut parts: Vec<Bool> = vec![contains(i, x, y).not(), Bool::and(&full)];
+                for (nx, ny) in neighbours(x, y) {
+                    parts.push(Bool::and(&[
+                        contains(i, nx, ny).clone(),
+                        distances[index(nx, ny)].lt(distance),
+                    ]));
+                }
+                solver.assert(&Bool::or(&parts));
+            }
+        }
+    }
+
+    if convex {
+        for i in 0..n {
+            // Rows
+            for y in 0..height {
+                for x1 in 0..width {
+                    for x3 in (x1 + 2)..width {
+                        for x2 in (x1 + 1)..x3 {
+                            solver.assert(&Bool::or(&[
+                                contains(i, x1, y).not(),
+                                contains(i, x3, y).not(),
+                                contains(i, x2, y).clone(),
+                            ]));
+                        }
+                    }
+                }
+            }
+
+            // Columns
+            for x in 0..width {
+                for y1 in 0..height {
+                    for y3 in (y1 + 2)..height {
+                        for y2 in (y1 + 1)..y3 {
+                            solver.assert(&Bool::or(&[
+                                contains(i, x, y1).not(),
+                                contains(i, x, y3).not(),
+                                contains(i, x, y2).clone(),
+                            ]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(symmetry) = symmetry {
+        let (shift, rotate): (usize, fn(usize, usize, usize, usize) -> (usize, usize)) =
+            match symmetry {
+                Symmetry::Rotate180 => {
+                    if n % 2 != 0 {
+                        return Ok(Vec::new());
+                    }
+                    (n / 2, |x, y, width, height| (width - 1 - x, height - 1 - y))
+                }
+                Symmetry::Rotate90 => {
+                    if n % 4 != 0 || width != height {
+                        return Ok(Vec::new());
+                    }
+                    (n / 4, |x, y, _, height| (height - 1 - y, x))
+                }
+            };
+
+        for i in 0..n {
+            for y in 0..height {
+                for x in 0..width {
+                    let (rx, ry) = rotate(x, y, width, height);
+                    solver.assert(&equal(contains(i, x, y), contains((i + shift) % n, rx, ry)));
+                }
+            }
+        }
+    }
+
+    let mut diagrams = Vec::new();
+    while diagrams.len() < limit && solver.check() == SatResult::Sat {
+        let Some(model) = solver.get_model() else {
+            break;
+        };
+
+        let mut polyominos = Vec::with_capacity(n);
+        let mut blocking: Vec<Bool> = Vec::new();
+        for variables in &cell_variables {
+            let mut poly = Polyomino::empty(width, height);
+            for (j, variable) in variables.iter().enumerate() {
+                let value = model.eval(variable, true).and_then(|x| x.as_bool()).unwrap();
+                poly[(j % width, j / width)] = value;
+                blocking.push(if value { variable.not() } else { variable.clone() });
+            }
+            polyominos.push(poly);
+        }
+
+        // Find a different diagram next time
+        solver.assert(&Bool::or(&blocking));
+
+        if let Ok(diagram) = Diagram::new(polyominos) {
+            diagrams.push(diagram);
+        }
+    }
+
+    Ok(diagrams)
+}
@@ -0,0 +1,85 @@
+use super::Diagram;
+use crate::polyomino::Polyomino;
+
+impl Polyomino {
+    // Move cell (x, y) to `f(x, y)` in a polyomino of size `width` by `height`.
+    pub(crate) fn map_cells(
+        &self,
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut out = Self::empty(width, height);
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                out[f(x, y)] = self[(x, y)];
+            }
+        }
+        out
+    }
+}
+
+impl Diagram {
+    fn map_cells(
+        &self,
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let polyominos = self.polyominos.iter().map(|poly| poly.map_cells(width, height, &f));
+        Self { width, height, polyominos: polyominos.collect() }
+    }
+
+    /// Rotate the diagram 90° clockwise.
+    #[must_use]
+    pub fn rotate90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Mirror the diagram, so that the leftmost column becomes the rightmost.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let (width, height) = (self.width, self.height);
+        self.map_cells(width, height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirror the diagram, so that the top row becomes the bottom row.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let (width, height) = (self.width, self.height);
+        self.map_cells(width, height, |x, y| (x, height - 1 - y))
+    }
+
+    /// Mirror the diagram along its diagonal, so that rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.map_cells(self.height, self.width, |x, y| (y, x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constants::d3::THREE, diagram::Diagram};
+
+    #[test]
+    fn transforms() {
+        let diagram = Diagram::from_letters("A AB B\nAC ABC BC\n. C .").unwrap();
+        let rotated = Diagram::from_letters(". AC A\nC ABC AB\n. BC B").unwrap();
+        assert_eq!(diagram.rotate90(), rotated);
+        assert_eq!(
+            diagram.flip_horizontal(),
+            Diagram::from_letters("B AB A\nBC ABC AC\n. C .").unwrap()
+        );
+        assert_eq!(
+            diagram.flip_vertical(),
+            Diagram::from_letters(". C .\nAC ABC BC\nA AB B").unwrap()
+        );
+        assert_eq!(diagram.transpose(), Diagram::from_letters("A AC .\nAB ABC C\nB BC .").unwrap());
+
+        let three: Diagram = THREE.into();
+        assert_eq!(three.rotate90().width(), three.height());
+        assert_eq!(three.rotate90().rotate90().rotate90().rotate90(), three);
+        assert_eq!(three.transpose().transpose(), three);
+        assert_eq!(three.flip_horizontal().flip_vertical(), three.rotate90().rotate90());
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Diagram, SetMask};
+
+// How many missing subsets `Diagram::validate` lists, since there can be up to
+// 2^26 of them
+const MAX_MISSING: usize = 4096;
+
+/// A set's polyomino with the wrong number of cells, see [`Validation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrongSize {
+    /// Index of the set.
+    pub set: usize,
+
+    /// Number of cells in the polyomino.
+    pub count: usize,
+
+    /// Number of cells the polyomino should have, `2^(n-1)` for a Venn
+    /// diagram.
+    pub expected: usize,
+}
+
+/// Report of everything that makes a [`Diagram`] an invalid Venn diagram,
+/// created by [`Diagram::validate`] or [`Diagram::validate_euler`].
+///
+/// Subsets are represented as a [`SetMask`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    /// Polyominos which don't consist of one cell for each expected subset
+    /// they are part of.
+    pub wrong_sizes: Vec<WrongSize>,
+
+    /// Expected subsets which aren't part of any cell, in increasing order.
+    /// [`Diagram::validate`] lists at most the first 4096 of them.
+    pub missing: Vec<SetMask>,
+
+    /// Number of expected subsets which aren't part of any cell.
+    pub missing_count: usize,
+
+    /// Subsets which are part of a cell, but weren't expected.
+    pub unexpected: Vec<SetMask>,
+
+    /// Subsets which are part of more than one cell.
+    pub duplicated: Vec<SetMask>,
+
+    /// Polyominos which consist of multiple components.
+    pub disconnected: Vec<usize>,
+
+    /// Polyominos which contain holes.
+    pub with_holes: Vec<usize>,
+
+    /// Number of cells which are part of at least one set.
+    pub area: usize,
+
+    /// If the area of the union of all polyominos is the number of expected
+    /// subsets, i.e. each expected subset is part of exactly one cell and no
+    /// other cells are used.
+    pub minimum_area: bool,
+}
+
+impl Validation {
+    /// Returns `true` if each polyomino has the right size and each expected
+    /// subset, and no other subset, is part of exactly one cell.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.wrong_sizes.is_empty()
+            && self.missing_count == 0
+            && self.unexpected.is_empty()
+            && self.duplicated.is_empty()
+    }
+
+    /// Returns `true` if the diagram is complete and each polyomino is
+    /// connected and without holes.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.is_complete() && self.disconnected.is_empty() && self.with_holes.is_empty()
+    }
+}
+
+impl Diagram {
+    /// Check if the Venn diagram is valid, returning a report of every
+    /// problem that was found.
+    ///
+    /// ```
+    /// use venn_diagrams::{constants::d3::THREE, diagram::Diagram};
+    ///
+    /// let diagram: Diagram = THREE.into();
+    /// let validation = diagram.validate();
+    /// assert!(validation.is_valid());
+    /// assert!(validation.minimum_area);
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Validation {
+        // The expected subsets are all masks below `1 << n`, which contain
+        // every mask of a cell. They are only enumerated until enough missing
+        // ones are found.
+        let n = self.n();
+        let expected = (1 << n) / 2;
+        self.validate_with(
+            |_| expected,
+            |_| true,
+            |counts| {
+                let missing = (1..1 << n).filter(|x| !counts.contains_key(x));
+                (missing.take(MAX_MISSING).collect(), (1 << n) - 1 - counts.len())
+            },
+            (1 << n) - 1,
+        )
+    }
+
+    /// Check if the diagram is a valid [Euler diagram][wp-euler], where only
+    /// the given subsets should be part of a cell. The empty subset is
+    /// ignored.
+    ///
+    /// ```
+    /// use venn_diagrams::diagram::Diagram;
+    ///
+    /// let diagram = Diagram::from_letters("A AB B BC C").unwrap();
+    /// assert!(!diagram.validate().is_complete());
+    /// assert!(diagram.validate_euler(&[0b001, 0b011, 0b010, 0b110, 0b100]).is_valid());
+    /// ```
+    ///
+    /// [wp-euler]: https://en.wikipedia.org/wiki/Euler_diagram
+    #[must_use]
+    pub fn validate_euler(&self, subsets: &[SetMask]) -> Validation {
+        let expected_subsets: BTreeSet<SetMask> =
+            subsets.iter().copied().filter(|x| *x != 0).collect();
+        self.validate_with(
+            |set| expected_subsets.iter().filter(|x| *x & (1 << set) != 0).count(),
+            |mask| expected_subsets.contains(&mask),
+            |counts| {
+                let missing: Vec<SetMask> =
+                    expected_subsets.iter().copied().filter(|x| !counts.contains_key(x)).collect();
+                let count = missing.len();
+                (missing, count)
+            },
+            expected_subsets.len(),
+        )
+    }
+
+    // Validate against the expected subsets, given by the number of them
+    // containing each set, whether a subset is expected, the listed and total
+    // number of missing subsets given the number of cells of each subset, and
+    // the number of expected subsets
+    fn validate_with(
+        &self,
+        expected_size: impl Fn(usize) -> usize,
+        is_expected: impl Fn(SetMask) -> bool,
+        missing: impl FnOnce(&BTreeMap<SetMask, usize>) -> (Vec<SetMask>, usize),
+        expected_count: usize,
+    ) -> Validation {
+        let mut wrong_sizes = Vec::new();
+        let mut disconnected = Vec::new();
+        let mut with_holes = Vec::new();
+        for (set, poly) in self.polyominos.iter().enumerate() {
+            let count = poly.count();
+            let expected = expected_size(set);
+            if count != expected {
+                wrong_sizes.push(WrongSize { set, count, expected });
+            }
+            if !poly.is_connected() {
+                disconnected.push(set);
+            }
+            if poly.has_holes() {
+                with_holes.push(set);
+            }
+        }
+
+        let mut counts: BTreeMap<SetMask, usize> = BTreeMap::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mask = self.mask_at(x, y);
+                if mask != 0 {
+                    *counts.entry(mask).or_default() += 1;
+                }
+            }
+        }
+
+        let (missing, missing_count) = missing(&counts);
+        let unexpected: Vec<SetMask> =
+            counts.keys().copied().filter(|x| !is_expected(*x)).collect();
+        let duplicated: Vec<SetMask> =
+            counts.iter().filter(|(_, count)| **count > 1).map(|(mask, _)| *mask).collect();
+        let area: usize = counts.values().sum();
+        let minimum_area = missing_count == 0 && unexpected.is_empty() && area == expected_count;
+
+        Validation {
+            wrong_sizes,
+            missing,
+            missing_count,
+            unexpected,
+            duplicated,
+            disconnected,
+            with_holes,
+            area,
+            minimum_area,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{d2::TWO, d3::THREE, d4::FOUR, d5::FIVE, d6::SIX, d7::SEVEN, d8::EIGHT};
+
+    #[test]
+    fn premade_are_valid() {
+        let diagrams: [Diagram; 7] = [
+            TWO.into(),
+            THREE.into(),
+            FOUR.into(),
+            FIVE.into(),
+            SIX.into(),
+            SEVEN.into(),
+            EIGHT.into(),
+        ];
+        for diagram in diagrams {
+            let validation = diagram.validate();
+            assert!(validation.is_valid());
+            assert!(validation.minimum_area);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let diagram = Diagram::from_letters(
+            "
+            A  AB ABC B .
+            AC .  AC  . C
+            ",
+        )
+        .unwrap();
+        let validation = diagram.validate();
+        assert_eq!(
+            validation.wrong_sizes,
+            vec![
+                WrongSize { set: 0, count: 5, expected: 4 },
+                WrongSize { set: 1, count: 3, expected: 4 },
+            ]
+        );
+        assert_eq!(validation.missing, vec![0b110]);
+        assert_eq!(validation.missing_count, 1);
+        assert!(validation.unexpected.is_empty());
+        assert_eq!(validation.duplicated, vec![0b101]);
+        assert_eq!(validation.disconnected, vec![2]);
+        assert!(validation.with_holes.is_empty());
+        assert_eq!(validation.area, 7);
+        assert!(!validation.minimum_area);
+        assert!(!validation.is_complete());
+    }
+
+    #[test]
+    fn many_sets() {
+        // Only some of the missing subsets are listed, out of 2^26 - 1
+        let grids: Vec<String> =
+            (0..26).map(|i| (0..26).map(|x| if x == i { '1' } else { '0' }).collect()).collect();
+        let grids: Vec<&str> = grids.iter().map(String::as_str).collect();
+        let diagram = Diagram::from_binary_grids(&grids).unwrap();
+        let validation = diagram.validate();
+        assert_eq!(validation.wrong_sizes.len(), 26);
+        assert_eq!(validation.area, 26);
+        assert_eq!(validation.missing_count, (1 << 26) - 1 - 26);
+        assert_eq!(validation.missing[..3], [0b011, 0b101, 0b110]);
+        assert_eq!(validation.missing.len(), MAX_MISSING);
+        assert!(!validation.is_complete());
+    }
+
+    #[test]
+    fn holes() {
+        let diagram = Diagram::from_binary_grids(&["111\n101\n111", "000\n010\n000"]).unwrap();
+        let validation = diagram.validate();
+        assert_eq!(validation.with_holes, vec![0]);
+        assert!(validation.disconnected.is_empty());
+        assert!(!validation.is_valid());
+    }
+}
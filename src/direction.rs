@@ -6,8 +6,6 @@ pub enum Edge {
 
 use std::mem;
 
-use Edge::*;
-
 impl Edge {
     pub fn new_horizontal(y: usize, x1: usize, x2: usize) -> Self {
         debug_assert!(x1 <= x2);
@@ -18,26 +16,32 @@ impl Edge {
         debug_assert!(y1 <= y2);
         Self::Vertical { x, y1, y2 }
     }
-
-    pub fn endpoints(&self) -> ((usize, usize), (usize, usize)) {
-        match *self {
-            Horizontal { y, x1, x2 } => ((x1, y), (x2, y)),
-            Vertical { x, y1, y2 } => ((x, y1), (x, y2)),
-        }
-    }
-
-    pub fn connected(&self, other: &Self) -> bool {
-        assert!(self != other);
-        let (p1, p2) = self.endpoints();
-        let (p3, p4) = other.endpoints();
-        p1 == p3 || p1 == p4 || p2 == p3 || p2 == p4
-    }
 }
 
+/// A horizontal or vertical edge between two points on the grid, part of the
+/// border of a polyomino in a [`LayoutConst`](crate::svg::LayoutConst). The
+/// inside of the polyomino is to the right of the edge.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum DirectedEdge {
-    Horizontal { y: usize, x_from: usize, x_to: usize },
-    Vertical { x: usize, y_from: usize, y_to: usize },
+    /// An edge from (`x_from`, `y`) to (`x_to`, `y`).
+    Horizontal {
+        /// Row of the edge.
+        y: usize,
+        /// Column of the start point.
+        x_from: usize,
+        /// Column of the end point.
+        x_to: usize,
+    },
+
+    /// An edge from (`x`, `y_from`) to (`x`, `y_to`).
+    Vertical {
+        /// Column of the edge.
+        x: usize,
+        /// Row of the start point.
+        y_from: usize,
+        /// Row of the end point.
+        y_to: usize,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -98,6 +102,9 @@ impl From<DirectedEdge> for Edge {
 }
 
 impl DirectedEdge {
+    /// Create an edge from `from` to `to`, if they are on the same row or
+    /// column.
+    #[must_use]
     pub fn from_endpoints(
         (x_from, y_from): (usize, usize),
         (x_to, y_to): (usize, usize),
@@ -111,7 +118,7 @@ impl DirectedEdge {
         }
     }
 
-    pub fn direction(&self) -> Direction {
+    pub(crate) fn direction(&self) -> Direction {
         match self {
             Self::Horizontal { x_from, x_to, .. } => {
                 if x_from < x_to {
@@ -130,6 +137,8 @@ impl DirectedEdge {
         }
     }
 
+    /// Start point of the edge.
+    #[must_use]
     pub fn from(&self) -> (usize, usize) {
         match *self {
             Self::Horizontal { y, x_from, .. } => (x_from, y),
@@ -137,6 +146,8 @@ impl DirectedEdge {
         }
     }
 
+    /// End point of the edge.
+    #[must_use]
     pub fn to(&self) -> (usize, usize) {
         match *self {
             Self::Horizontal { y, x_to, .. } => (x_to, y),
@@ -144,14 +155,25 @@ impl DirectedEdge {
         }
     }
 
-    pub fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         match *self {
             Self::Horizontal { x_from, x_to, .. } => x_from.abs_diff(x_to),
             Self::Vertical { y_from, y_to, .. } => y_from.abs_diff(y_to),
         }
     }
 
-    pub fn combine_directed(&self, other: &Self) -> Option<Self> {
+    /// The same edge, going in the opposite direction.
+    pub(crate) fn reversed(&self) -> Self {
+        Self::from_endpoints(self.to(), self.from()).unwrap()
+    }
+
+    /// Move both endpoints of the edge with `f`, which must keep horizontal
+    /// and vertical edges axis-aligned.
+    pub(crate) fn map_endpoints(&self, f: impl Fn((usize, usize)) -> (usize, usize)) -> Self {
+        Self::from_endpoints(f(self.from()), f(self.to())).unwrap()
+    }
+
+    pub(crate) fn combine_directed(&self, other: &Self) -> Option<Self> {
         let from1 = self.from();
         let to1 = self.to();
         let from2 = other.from();
@@ -71,6 +71,8 @@
 
 mod direction;
 
+pub mod coalition;
+pub mod codegen;
 pub mod constants;
 pub mod polyomino;
 pub mod svg;
@@ -78,3 +80,6 @@ pub mod svg;
 mod matrix;
 
 pub mod diagram;
+
+#[cfg(feature = "serde")]
+mod serialize;
@@ -0,0 +1,345 @@
+//! Command-line tool to render Venn diagrams, enabled by the `cli` feature.
+//!
+//! ```text
+//! venn-diagrams render <CONFIG> [-o <OUTPUT>]
+//! venn-diagrams codegen <NAME> <DIAGRAM> [--greedy] [-o <OUTPUT>]
+//! ```
+//!
+//! `render` draws a Venn diagram as an SVG, or depending on the extension of
+//! `OUTPUT` as interactive HTML (`.html`), a TikZ picture (`.tex`), a PDF
+//! (`.pdf`), an EPS file (`.eps`) or a PNG image (`.png`). `CONFIG` is a TOML
+//! or JSON file (depending on its extension) such as
+//!
+//! ```toml
+//! layout = "optimized"
+//!
+//! [[sets]]
+//! name = "Red"
+//! value = 107
+//! color = "#EE2020"
+//!
+//! [[sets]]
+//! name = "Blue"
+//! value = 68
+//! color = "#1B49DD"
+//!
+//! [config]
+//! line_width = 0.1
+//! legend = "right"
+//! ```
+//!
+//! The premade diagram for the number of sets is used, unless `diagram` is
+//! set to for example `{ premade = 3 }` or `{ letters = "A AB B" }`. Every
+//! field of `DiagramConfig` can be set in `config`, and the names of the sets
+//! are shown in the legend if `legend` is set to `"right"` or `"bottom"`,
+//! which PNG images don't support.
+//!
+//! `codegen` creates a Rust module with the diagram as the constant `NAME`,
+//! and its optimized layout as `LAYOUT_OPTIMIZED_NAME`, using
+//! `venn_diagrams::codegen`. `DIAGRAM` is a file in the format of
+//! `Diagram::from_letters`, or a JSON file. Optimizing requires the
+//! `optimize` feature, otherwise `--greedy` can be used to create the layout
+//! `LAYOUT_NAME` greedily.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use serde::Deserialize;
+use venn_diagrams::{
+    codegen, constants,
+    diagram::Diagram,
+    svg::{
+        DiagramConfig, EpsRenderer, PdfRenderer, PngRenderer, Renderer, SvgRenderer, TikzRenderer,
+    },
+};
+
+const USAGE: &str = "Usage:
+    venn-diagrams render <CONFIG> [-o <OUTPUT>]
+    venn-diagrams codegen <NAME> <DIAGRAM> [--greedy] [-o <OUTPUT>]";
+
+/// One set of the diagram.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Set {
+    name: String,
+    value: f64,
+    color: String,
+}
+
+/// Which Venn diagram to draw.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum DiagramInput {
+    /// One of the diagrams in `constants`, by number of sets.
+    Premade(usize),
+
+    /// A diagram in the format of `Diagram::from_letters`.
+    Letters(String),
+}
+
+/// How to lay out the edges of the diagram.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LayoutMethod {
+    #[default]
+    Greedy,
+    Optimized,
+}
+
+/// Content of a config file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Input {
+    sets: Vec<Set>,
+    diagram: Option<DiagramInput>,
+    #[serde(default)]
+    layout: LayoutMethod,
+    #[serde(default)]
+    config: DiagramConfig,
+}
+
+fn parse_input(path: &Path) -> Result<Input, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("can't read {}: {e}", path.display()))?;
+    if path.extension().is_some_and(|x| x == "json") {
+        serde_json::from_str(&content).map_err(|e| format!("invalid config: {e}"))
+    } else {
+        toml::from_str(&content).map_err(|e| format!("invalid config: {e}"))
+    }
+}
+
+/// What `render` outputs, decided by the extension of the output file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Svg,
+    Html,
+    Tikz,
+    Pdf,
+    Eps,
+    Png,
+}
+
+impl Format {
+    fn from_output(output: Option<&String>) -> Self {
+        match output {
+            Some(output) if output.ends_with(".html") => Self::Html,
+            Some(output) if output.ends_with(".tex") => Self::Tikz,
+            Some(output) if output.ends_with(".pdf") => Self::Pdf,
+            Some(output) if output.ends_with(".eps") => Self::Eps,
+            Some(output) if output.ends_with(".png") => Self::Png,
+            _ => Self::Svg,
+        }
+    }
+}
+
+fn render(input: &Input, format: Format) -> Result<Vec<u8>, String> {
+    let n = input.sets.len();
+    let premade = |n: usize| {
+        constants::diagram(n).ok_or_else(|| format!("there is no premade diagram for {n} sets"))
+    };
+    let (diagram, premade) = match &input.diagram {
+        None => (premade(n)?, Some(n)),
+        Some(DiagramInput::Premade(n)) => (premade(*n)?, Some(*n)),
+        Some(DiagramInput::Letters(letters)) => {
+            (Diagram::from_letters(letters).map_err(|e| format!("invalid diagram: {e}"))?, None)
+        }
+    };
+
+    if diagram.n() != n {
+        return Err(format!("the diagram has {} sets, but {n} sets are given", diagram.n()));
+    }
+    for set in &input.sets {
+        if !(set.value.is_finite() && set.value >= 0.0) {
+            return Err(format!("set {:?} has invalid value {}", set.name, set.value));
+        }
+    }
+    let total: f64 = input.sets.iter().map(|set| set.value).sum();
+    if total <= 0.0 {
+        return Err(String::from("the sum of all values must be positive"));
+    }
+
+    let layout = match (input.layout, premade) {
+        (LayoutMethod::Greedy, _) => diagram.layout_greedy(),
+        (LayoutMethod::Optimized, Some(n)) => constants::layout_optimized(n).unwrap(),
+        #[cfg(feature = "optimize")]
+        (LayoutMethod::Optimized, None) => diagram.layout_optimize(),
+        #[cfg(not(feature = "optimize"))]
+        (LayoutMethod::Optimized, None) => {
+            return Err(String::from(
+                "optimized layouts of custom diagrams require the `optimize` feature",
+            ));
+        }
+    };
+
+    let values: Vec<f64> = input.sets.iter().map(|set| set.value / total).collect();
+    let colors: Vec<&str> = input.sets.iter().map(|set| set.color.as_str()).collect();
+    let mut config = input.config.clone();
+    if config.names.is_empty() {
+        config.names = input.sets.iter().map(|set| set.name.clone()).collect();
+    }
+    let scene = layout.scene(&values, &colors, &config).map_err(|e| e.to_string())?;
+    match format {
+        Format::Svg => Ok(SvgRenderer.render(&scene).to_string().into_bytes()),
+        Format::Html => Ok(layout.to_html(&values, &colors, &config).into_bytes()),
+        Format::Tikz => {
+            let tikz = TikzRenderer::default().render(&scene).map_err(|e| e.to_string())?;
+            Ok(tikz.into_bytes())
+        }
+        Format::Pdf => PdfRenderer::default().render(&scene).map_err(|e| e.to_string()),
+        Format::Eps => {
+            let eps = EpsRenderer::default().render(&scene).map_err(|e| e.to_string())?;
+            Ok(eps.into_bytes())
+        }
+        Format::Png => {
+            // As wide as the SVG is shown, or 800 pixels
+            let width = config.width_mul.map_or(800.0, |mul| mul * scene.geometry.bounds.2);
+            let renderer = PngRenderer { width: width.round() as u32 };
+            renderer.render(&scene).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(feature = "optimize")]
+fn optimized_module(name: &str, diagram: Diagram) -> Result<String, String> {
+    codegen::optimized_module(name, diagram).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "optimize"))]
+fn optimized_module(_: &str, _: Diagram) -> Result<String, String> {
+    Err(String::from("optimized layouts require the `optimize` feature, or use `--greedy`"))
+}
+
+fn generate(name: &str, path: &Path, greedy: bool) -> Result<String, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("can't read {}: {e}", path.display()))?;
+    let diagram = if path.extension().is_some_and(|x| x == "json") {
+        serde_json::from_str(&content).map_err(|e| format!("invalid diagram: {e}"))?
+    } else {
+        Diagram::from_letters(&content).map_err(|e| format!("invalid diagram: {e}"))?
+    };
+
+    if greedy {
+        let layout = diagram.layout_greedy();
+        codegen::module(name, &format!("LAYOUT_{name}"), &layout).map_err(|e| e.to_string())
+    } else {
+        optimized_module(name, diagram)
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (args, output) = match args {
+        [args @ .., flag, output] if flag == "-o" => (args, Some(output)),
+        _ => (args, None),
+    };
+
+    let out = match args {
+        [command, config] if command == "render" => {
+            render(&parse_input(Path::new(config))?, Format::from_output(output))?
+        }
+        [command, name, diagram] if command == "codegen" => {
+            generate(name, Path::new(diagram), false)?.into_bytes()
+        }
+        [command, name, diagram, flag] if command == "codegen" && flag == "--greedy" => {
+            generate(name, Path::new(diagram), true)?.into_bytes()
+        }
+        _ => return Err(String::from(USAGE)),
+    };
+
+    match output {
+        Some(output) => fs::write(output, out).map_err(|e| format!("can't write {output}: {e}")),
+        None => io::stdout().write_all(&out).map_err(|e| format!("can't write output: {e}")),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Input {
+        toml::from_str(s).unwrap()
+    }
+
+    fn render_text(input: &Input, format: Format) -> String {
+        String::from_utf8(render(input, format).unwrap()).unwrap()
+    }
+
+    const SETS: &str = r##"
+        [[sets]]
+        name = "Red"
+        value = 107
+        color = "#EE2020"
+
+        [[sets]]
+        name = "Yellow"
+        value = 73
+        color = "#DDDD00"
+
+        [[sets]]
+        name = "Blue"
+        value = 68
+        color = "#1B49DD"
+    "##;
+
+    #[test]
+    fn premade() {
+        let input = parse(&format!("layout = \"optimized\"\n{SETS}\n[config]\nline_width = 0.1"));
+        let svg = render_text(&input, Format::Svg);
+        assert!(svg.contains(r##"stroke="#EE2020" stroke-width="0.1""##));
+        assert!(!svg.contains("Yellow 29.4%"));
+        let input = parse(&format!("{SETS}\n[config]\nlegend = \"bottom\""));
+        assert!(render_text(&input, Format::Svg).contains("Yellow 29.4%"));
+        assert!(
+            render(&parse(&format!("diagram = {{ premade = 4 }}\n{SETS}")), Format::Svg).is_err()
+        );
+    }
+
+    #[test]
+    fn letters() {
+        let input = parse(&format!("diagram = {{ letters = \"A AB B\\nAC ABC BC\" }}\n{SETS}"));
+        assert!(render(&input, Format::Svg).is_ok());
+        assert!(render_text(&input, Format::Html).contains("<script>"));
+        assert!(render_text(&input, Format::Tikz).contains("\\definecolor{venn-set-0}"));
+        assert!(render_text(&input, Format::Eps).starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+        assert!(render(&input, Format::Pdf).unwrap().starts_with(b"%PDF-1.4"));
+        assert!(render(&input, Format::Png).unwrap().starts_with(b"\x89PNG"));
+        let input = parse(&format!("diagram = {{ letters = \"A AB B\" }}\n{SETS}"));
+        assert!(render(&input, Format::Svg).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(toml::from_str::<Input>(&format!("unknown = 1\n{SETS}")).is_err());
+        assert!(render(&parse(&SETS.replace("107", "-1")), Format::Svg).is_err());
+        assert!(render(&parse(&format!("{SETS}\n[config]\nradius = 0")), Format::Svg).is_err());
+        let input = parse(&format!("{SETS}\n[config]\nwidth_mul = 1e9"));
+        assert!(render(&input, Format::Png).is_err());
+        let input = parse(&format!("{SETS}\n[config]\nlegend = \"right\""));
+        assert!(render(&input, Format::Png).is_err());
+        assert!(run(&[String::from("draw")]).is_err());
+    }
+
+    #[test]
+    fn codegen() {
+        let path = std::env::temp_dir().join("venn_diagrams_codegen_test.txt");
+        fs::write(&path, "A AB B").unwrap();
+        let code = generate("TWO", &path, true).unwrap();
+        assert!(code.contains("pub const LAYOUT_TWO: LayoutConst<8, 2, 3, 1>"));
+        assert!(generate("two", &path, true).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
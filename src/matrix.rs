@@ -11,10 +11,6 @@ impl<T: Clone> Matrix<T> {
     pub fn new(width: usize, height: usize, default: T) -> Self {
         Self { width, values: vec![default; width * height] }
     }
-
-    pub fn row(&self, i: usize) -> &[T] {
-        &self.values[i * self.width..((i + 1) * self.width)]
-    }
 }
 
 impl<T> Index<(usize, usize)> for Matrix<T> {
@@ -8,6 +8,8 @@
 
 use std::ops::{Index, IndexMut};
 
+use crate::diagram::DiagramError;
+
 /// A statically allocated polyomino.
 ///
 /// It has two generic parameters:
@@ -100,6 +102,118 @@ impl Polyomino {
     pub fn empty(x: usize, y: usize) -> Self {
         Self { width: x, height: y, values: vec![false; x * y] }
     }
+
+    /// Create a polyomino from a string, where each line is a sequence of `0`
+    /// and `1`. Leading and trailing whitespace of each line, and empty lines,
+    /// are ignored.
+    /// ```
+    /// use venn_diagrams::polyomino::Polyomino;
+    ///
+    /// let s = "
+    ///     1000
+    ///     1100
+    /// ";
+    /// let polyomino = Polyomino::from_binary_str(s).unwrap();
+    /// assert_eq!(polyomino.width(), 4);
+    /// assert_eq!(polyomino.height(), 2);
+    /// ```
+    pub fn from_binary_str(s: &str) -> Result<Self, DiagramError> {
+        let rows: Vec<&str> = s.lines().map(str::trim).filter(|row| !row.is_empty()).collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let height = rows.len();
+
+        let mut values = Vec::with_capacity(width * height);
+        for (y, row) in rows.iter().enumerate() {
+            let row_width = row.chars().count();
+            if row_width != width {
+                return Err(DiagramError::RaggedRow {
+                    set: None,
+                    y,
+                    width: row_width,
+                    expected: width,
+                });
+            }
+            for (x, character) in row.chars().enumerate() {
+                let value = match character {
+                    '0' => false,
+                    '1' => true,
+                    _ => return Err(DiagramError::InvalidCharacter { set: None, x, y, character }),
+                };
+                values.push(value);
+            }
+        }
+
+        Ok(Self { width, height, values })
+    }
+
+    /// Returns `true` if the polyomino doesn't contain any cell.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !self.values.contains(&true)
+    }
+
+    /// Number of cells in the polyomino.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.values.iter().filter(|v| **v).count()
+    }
+
+    /// Returns `true` if every cell can be reached from every other cell, by
+    /// only moving horizontally or vertically. An empty polyomino is
+    /// connected.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.values.iter().position(|v| *v) else {
+            return true;
+        };
+        let mut seen = vec![false; self.values.len()];
+        self.fill((start % self.width, start / self.width), true, &mut seen);
+        self.values.iter().zip(&seen).all(|(v, s)| !v || *s)
+    }
+
+    /// Returns `true` if the polyomino encloses any cell, i.e. there is a cell
+    /// outside the polyomino which can't reach the border of the grid without
+    /// passing through the polyomino.
+    #[must_use]
+    pub fn has_holes(&self) -> bool {
+        if self.values.is_empty() {
+            return false;
+        }
+        let mut seen = vec![false; self.values.len()];
+        for x in 0..self.width {
+            self.fill((x, 0), false, &mut seen);
+            self.fill((x, self.height - 1), false, &mut seen);
+        }
+        for y in 0..self.height {
+            self.fill((0, y), false, &mut seen);
+            self.fill((self.width - 1, y), false, &mut seen);
+        }
+        self.values.iter().zip(&seen).any(|(v, s)| !v && !s)
+    }
+
+    // Mark every cell equal to `value` which can be reached from `start`.
+    fn fill(&self, start: (usize, usize), value: bool, seen: &mut [bool]) {
+        let mut stack = vec![start];
+        while let Some((x, y)) = stack.pop() {
+            let i = y * self.width + x;
+            if seen[i] || self.values[i] != value {
+                continue;
+            }
+            seen[i] = true;
+            if x != 0 {
+                stack.push((x - 1, y));
+            }
+            if y != 0 {
+                stack.push((x, y - 1));
+            }
+            if x + 1 != self.width {
+                stack.push((x + 1, y));
+            }
+            if y + 1 != self.height {
+                stack.push((x, y + 1));
+            }
+        }
+    }
 }
 
 impl Clone for Polyomino {
@@ -0,0 +1,413 @@
+//! Serialization of diagrams, layouts and configs, enabled by the `serde` feature.
+//!
+//! Diagrams, layouts and configs are stored together with a version number,
+//! so that the format can change without breaking earlier files. Every value
+//! is validated when deserialized, and unknown fields of configs are
+//! rejected.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use crate::{
+    diagram::Diagram,
+    direction::DirectedEdge,
+    polyomino::Polyomino,
+    svg::{
+        CirclePlacement, CornerStyle, DiagramConfig, Layout, LegendPosition, Styling,
+        ThresholdBand, get_polys,
+    },
+};
+
+// The current version of the format of `Diagram` and `Layout`
+const VERSION: u32 = 1;
+
+// The current version of the format of `DiagramConfig`. Version 1 had the
+// fields `circle_below`, `circle_edge` and `circle_above` instead of
+// `thresholds`, and was stored without a version.
+const CONFIG_VERSION: u32 = 2;
+
+fn check_version<E: serde::de::Error>(version: u32, expected: u32) -> Result<(), E> {
+    if version == expected {
+        Ok(())
+    } else {
+        Err(E::custom(format!("unsupported version {version}, expected {expected}")))
+    }
+}
+
+// A polyomino is stored as rows of `0` and `1`
+impl Serialize for Polyomino {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rows: Vec<String> = (0..self.height())
+            .map(|y| (0..self.width()).map(|x| if self[(x, y)] { '1' } else { '0' }).collect())
+            .collect();
+        rows.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Polyomino {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows: Vec<String> = Vec::deserialize(deserializer)?;
+        if rows.iter().any(|row| row.trim().is_empty()) {
+            return Err(D::Error::custom("empty row in polyomino"));
+        }
+        Self::from_binary_str(&rows.join("\n")).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiagramRepr<P> {
+    version: u32,
+    polyominos: Vec<P>,
+}
+
+impl Serialize for Diagram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DiagramRepr { version: VERSION, polyominos: self.polyominos.iter().collect() }
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Diagram {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let DiagramRepr { version, polyominos } =
+            DiagramRepr::<Polyomino>::deserialize(deserializer)?;
+        check_version(version, VERSION)?;
+        Self::new(polyominos).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EdgeRepr {
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+impl Serialize for DirectedEdge {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EdgeRepr { from: self.from(), to: self.to() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DirectedEdge {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let EdgeRepr { from, to } = EdgeRepr::deserialize(deserializer)?;
+        match Self::from_endpoints(from, to) {
+            Some(edge) if from != to => Ok(edge),
+            _ => Err(D::Error::custom(format!("invalid edge from {from:?} to {to:?}"))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LayoutEdgeRepr {
+    #[serde(flatten)]
+    edge: DirectedEdge,
+    offset: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LayoutRepr<D> {
+    version: u32,
+    diagram: D,
+    paths: Vec<Vec<LayoutEdgeRepr>>,
+}
+
+impl Serialize for Layout {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let paths = self
+            .combined_paths
+            .iter()
+            .zip(&self.offsets)
+            .map(|(path, offsets)| {
+                let edges = path.iter().zip(offsets);
+                edges.map(|(&edge, &offset)| LayoutEdgeRepr { edge, offset }).collect()
+            })
+            .collect();
+        LayoutRepr { version: VERSION, diagram: &self.diagram, paths }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Layout {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let LayoutRepr { version, diagram, paths } =
+            LayoutRepr::<Diagram>::deserialize(deserializer)?;
+        check_version(version, VERSION)?;
+
+        let (combined_paths, offsets) = paths
+            .into_iter()
+            .map(|path| path.into_iter().map(|x| (x.edge, x.offset)).unzip())
+            .unzip();
+        let layout = Self {
+            width: diagram.width(),
+            height: diagram.height(),
+            combined_paths,
+            offsets,
+            diagram,
+        };
+        check_paths(&layout).map_err(D::Error::custom)?;
+        Ok(layout)
+    }
+}
+
+// A config has the same fields as `DiagramConfig`, where the version may be
+// left out in configs which are written by hand, such as the ones of the CLI
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigRepr {
+    version: u32,
+    line_width: f64,
+    radius: f64,
+    thresholds: Vec<ThresholdBand>,
+    circle_placement: CirclePlacement,
+    corner_style: CornerStyle,
+    corner_offset: f64,
+    width_mul: Option<f64>,
+    height_mul: Option<f64>,
+    id: Option<usize>,
+    fill_opacity: f64,
+    legend: LegendPosition,
+    names: Vec<String>,
+    styling: Styling,
+}
+
+impl Default for ConfigRepr {
+    fn default() -> Self {
+        DiagramConfig::default().into()
+    }
+}
+
+impl From<DiagramConfig> for ConfigRepr {
+    fn from(config: DiagramConfig) -> Self {
+        let DiagramConfig {
+            line_width,
+            radius,
+            thresholds,
+            circle_placement,
+            corner_style,
+            corner_offset,
+            width_mul,
+            height_mul,
+            id,
+            fill_opacity,
+            legend,
+            names,
+            styling,
+        } = config;
+        Self {
+            version: CONFIG_VERSION,
+            line_width,
+            radius,
+            thresholds,
+            circle_placement,
+            corner_style,
+            corner_offset,
+            width_mul,
+            height_mul,
+            id,
+            fill_opacity,
+            legend,
+            names,
+            styling,
+        }
+    }
+}
+
+impl Serialize for DiagramConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConfigRepr::from(self.clone()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DiagramConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ConfigRepr {
+            version,
+            line_width,
+            radius,
+            thresholds,
+            circle_placement,
+            corner_style,
+            corner_offset,
+            width_mul,
+            height_mul,
+            id,
+            fill_opacity,
+            legend,
+            names,
+            styling,
+        } = ConfigRepr::deserialize(deserializer)?;
+        check_version(version, CONFIG_VERSION)?;
+        Ok(Self {
+            line_width,
+            radius,
+            thresholds,
+            circle_placement,
+            corner_style,
+            corner_offset,
+            width_mul,
+            height_mul,
+            id,
+            fill_opacity,
+            legend,
+            names,
+            styling,
+        })
+    }
+}
+
+// Every edge of length 1 in `path`, as a pair of endpoints
+fn unit_edges(path: &[DirectedEdge]) -> Vec<((usize, usize), (usize, usize))> {
+    let mut out = Vec::new();
+    for edge in path {
+        let (mut x, mut y) = edge.from();
+        while (x, y) != edge.to() {
+            let (x_to, y_to) = match edge.to() {
+                (x_to, _) if x_to > x => (x + 1, y),
+                (x_to, _) if x_to < x => (x - 1, y),
+                (_, y_to) if y_to > y => (x, y + 1),
+                _ => (x, y - 1),
+            };
+            out.push(((x, y), (x_to, y_to)));
+            (x, y) = (x_to, y_to);
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+// The paths of a layout must consist of cycles of perpendicular edges, which
+// together form the border of each polyomino, so that it can be rendered.
+fn check_paths(layout: &Layout) -> Result<(), String> {
+    let Layout { width, height, combined_paths, offsets, diagram } = layout;
+    if combined_paths.len() != diagram.n() {
+        return Err(format!("expected {} paths, found {}", diagram.n(), combined_paths.len()));
+    }
+
+    let polys = get_polys(*width, *height, &diagram.polyominos);
+    for (i, path) in combined_paths.iter().enumerate() {
+        debug_assert!(path.len() == offsets[i].len());
+        let mut start = 0;
+        for (j, edge) in path.iter().enumerate() {
+            let ((x1, y1), (x2, y2)) = (edge.from(), edge.to());
+            if x1.max(x2) > *width || y1.max(y2) > *height {
+                return Err(format!("edge {j} of path {i} is outside of the diagram"));
+            }
+
+            let next = if edge.to() == path[start].from() {
+                let first = start;
+                start = j + 1;
+                &path[first]
+            } else {
+                match path.get(j + 1) {
+                    Some(next) if next.from() == edge.to() => next,
+                    _ => return Err(format!("edge {j} of path {i} isn't connected to the next")),
+                }
+            };
+            if edge.direction().opposite() == next.direction()
+                || edge.direction() == next.direction()
+            {
+                return Err(format!("edge {j} of path {i} isn't perpendicular to the next"));
+            }
+        }
+
+        if start != path.len() {
+            return Err(format!("path {i} doesn't end with a closed cycle"));
+        }
+
+        if unit_edges(path) != unit_edges(&polys[i]) {
+            return Err(format!("path {i} isn't the border of polyomino {i}"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{d3::THREE, d5::LAYOUT_OPTIMIZED_FIVE},
+        svg::DiagramConfig,
+    };
+
+    #[test]
+    fn diagram() {
+        let diagram: Diagram = THREE.into();
+        let json = serde_json::to_string(&diagram).unwrap();
+        assert!(json.starts_with(r#"{"version":1,"polyominos":[["#));
+        assert_eq!(serde_json::from_str::<Diagram>(&json).unwrap(), diagram);
+
+        let invalid = [
+            r#"{"version":2,"polyominos":[["1"]]}"#,
+            r#"{"version":1,"polyominos":[]}"#,
+            r#"{"version":1,"polyominos":[["10"],["1"]]}"#,
+            r#"{"version":1,"polyominos":[["12"]]}"#,
+            r#"{"version":1,"polyominos":[["10", "1"]]}"#,
+        ];
+        for json in invalid {
+            assert!(serde_json::from_str::<Diagram>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let layout: Layout = LAYOUT_OPTIMIZED_FIVE.into();
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<Layout>(&json).unwrap(), layout);
+
+        let two = r#"{"version":1,"diagram":{"version":1,"polyominos":[["110"],["011"]]},"paths":[
+            [{"from":[0,1],"to":[0,0],"offset":0},{"from":[0,0],"to":[2,0],"offset":0},
+             {"from":[2,0],"to":[2,1],"offset":0},{"from":[2,1],"to":[0,1],"offset":0}],
+            [{"from":[1,1],"to":[1,0],"offset":1},{"from":[1,0],"to":[3,0],"offset":1},
+             {"from":[3,0],"to":[3,1],"offset":0},{"from":[3,1],"to":[1,1],"offset":1}]]}"#;
+        let layout = serde_json::from_str::<Layout>(two).unwrap();
+        let _ = layout.to_svg(&[0.5, 0.5], &["red", "blue"], &DiagramConfig::default());
+
+        let invalid = [
+            // Wrong version
+            two.replacen(r#""version":1"#, r#""version":0"#, 1),
+            // Wrong polyomino
+            two.replace(r#"["011"]"#, r#"["001"]"#),
+            // Missing path
+            String::from(
+                r#"{"version":1,"diagram":{"version":1,"polyominos":[["110"],["011"]]},"paths":[[
+                {"from":[0,1],"to":[0,0],"offset":0},{"from":[0,0],"to":[2,0],"offset":0},
+                {"from":[2,0],"to":[2,1],"offset":0},{"from":[2,1],"to":[0,1],"offset":0}]]}"#,
+            ),
+            // Disconnected edges
+            two.replacen(r#""to":[2,0]"#, r#""to":[1,0]"#, 1),
+            // Outside of the diagram
+            two.replace("[3,", "[4,"),
+            // Zero length edge
+            two.replacen(r#""to":[0,0]"#, r#""to":[0,1]"#, 1),
+        ];
+        for json in &invalid {
+            assert!(serde_json::from_str::<Layout>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn config() {
+        let config = DiagramConfig { line_width: 0.1, ..DiagramConfig::default() };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: DiagramConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{parsed:?}"), format!("{config:?}"));
+
+        assert!(json.starts_with(r#"{"version":2,"line_width":0.1,"#));
+
+        // Missing fields, and a missing version, use their default value
+        let parsed: DiagramConfig = serde_json::from_str(r#"{"line_width":0.1}"#).unwrap();
+        assert_eq!(format!("{parsed:?}"), format!("{config:?}"));
+
+        let invalid = [
+            // Wrong version
+            r#"{"version":1,"line_width":0.1}"#,
+            // A field of version 1
+            r#"{"circle_below":{"opacity":0.3,"color":"red"}}"#,
+            // Unknown field of a band
+            r#"{"thresholds":[{"threshold":0.5,"circle":{"opacity":1,"color":"red"},"edge":null}]}"#,
+        ];
+        for json in invalid {
+            assert!(serde_json::from_str::<DiagramConfig>(json).is_err(), "{json}");
+        }
+    }
+}
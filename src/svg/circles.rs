@@ -1,20 +1,39 @@
 use std::f64::{self, consts::PI};
 
-use svg::node::element::{Circle, Group, Path, SVG, path::Data};
+use itertools::Itertools;
+use svg::node::element::{Circle, Group, Path, Title, path::Data};
 
-use super::{InnerOffset, config::DiagramConfig};
+use super::{InnerOffset, Pie, Point, Scene, Slice};
+use crate::coalition::Coalition;
 
-/// Configuration for a circle around a pie chart, see [`DiagramConfig`].
+/// Configuration for a circle around a pie chart, see
+/// [`DiagramConfig`](super::DiagramConfig).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct CircleConfig {
-    opacity: f64,
-    color: String,
+    pub(crate) opacity: f64,
+    pub(crate) color: String,
 }
 
 impl CircleConfig {
+    /// Create a circle with the given opacity and color.
+    #[must_use]
     pub fn new(opacity: f64, color: String) -> Self {
         Self { opacity, color }
     }
+
+    /// Opacity of the circle and the pie chart inside it.
+    #[must_use]
+    pub fn opacity(&self) -> f64 {
+        self.opacity
+    }
+
+    /// Color of the circle.
+    #[must_use]
+    pub fn color(&self) -> &str {
+        &self.color
+    }
 }
 
 fn x_pos(angle: f64) -> f64 {
@@ -26,15 +45,10 @@ fn y_pos(angle: f64) -> f64 {
 }
 
 // Outline piece of pie
-fn piece(cx: f64, cy: f64, r: f64, start: f64, end: f64) -> Path {
-    // They are both positive
-    debug_assert!(0.0 <= start);
-    debug_assert!(0.0 <= end);
-    debug_assert!(start <= end);
-
-    // We clamp start and end, sometimes end is slightly larger than TAU
-    let start = start.clamp(0.0, f64::consts::TAU);
-    let end = end.clamp(0.0, f64::consts::TAU);
+fn piece(center: Point, r: f64, slice: &Slice) -> Path {
+    let (cx, cy) = center;
+    let Slice { start, end, .. } = *slice;
+    debug_assert!(0.0 <= start && start <= end && end <= f64::consts::TAU);
 
     let start_x = cx + x_pos(start) * r;
     let start_y = cy - y_pos(start) * r;
@@ -55,46 +69,58 @@ fn piece(cx: f64, cy: f64, r: f64, start: f64, end: f64) -> Path {
     Path::new().set("d", data)
 }
 
-pub(super) fn draw_circle(
-    cx: f64,
-    cy: f64,
-    mask: &[bool],
-    out: SVG,
-    config: &DiagramConfig,
-    values: &[f64],
-    colors: &[&str],
-) -> SVG {
-    let n = mask.len();
-    debug_assert!(values.len() == n && colors.len() == n);
-    let radius = config.radius;
-    let mut group = Group::new();
-
-    let coalition: Coalition = Coalition::from_values(mask, values);
-
-    let mut added = 0.0;
-    for i in 0..n {
-        if !mask[i] {
-            continue;
+// Draw a pie chart, with a tooltip and data attributes describing the
+// coalition
+pub(super) fn draw_circle(pie: &Pie, scene: &Scene<'_>) -> Group {
+    let Scene { values, seats, colors, config, .. } = scene;
+    let sets = || pie.slices.iter().map(|slice| slice.set);
+
+    let names = sets().map(|i| config.set_name(i)).join(", ");
+    let title = match seats {
+        Some(seats) => format!("{names}: {} of {} seats", seats.seats(pie.subset), seats.total()),
+        None => {
+            let total: f64 = sets().map(|i| values[i]).sum();
+            format!("{names}: {:.1}%", 100.0 * total)
         }
-        let size = values[i];
-        let color = colors[i];
+    };
+    let classes = config.styling.uses_classes();
+    let mut group = Group::new().set("data-subset", pie.subset).add(Title::new(title));
+    let mut group_class = String::from("venn-region");
+    if let Some(class) = pie.coalition {
+        let class = match class {
+            Coalition::Below => "below",
+            Coalition::Edge => "edge",
+            Coalition::Above => "above",
+        };
+        group = group.set("data-coalition", class);
+        group_class += &format!(" venn-coalition-{class}");
+    }
 
-        let end = added + size;
+    for slice in &pie.slices {
+        let i = slice.set;
+        let piece = piece(pie.center, pie.radius, slice);
+        let out = if classes {
+            piece.set("class", format!("venn-set venn-set-{i} venn-piece"))
+        } else {
+            piece.set("fill", colors[i]).set("stroke", "none")
+        };
+        group = group.add(out);
+    }
 
-        let piece = piece(cx, cy, 2.0 * radius, f64::consts::TAU * added, f64::consts::TAU * end);
+    let (Some(band), Some(circle_config)) = (pie.band, pie.circle(config)) else {
+        return if classes { group.set("class", group_class) } else { group };
+    };
 
-        added = end;
+    let (cx, cy) = pie.center;
+    let circle = Circle::new().set("r", pie.radius).set("cx", cx).set("cy", cy);
 
-        let out = piece.set("fill", color).set("stroke", "none");
-        group = group.add(out);
+    if classes {
+        let edge = if pie.edge { "-edge" } else { "" };
+        group_class += &format!(" venn-band-{band}{edge}");
+        return group.set("class", group_class).add(circle.set("class", "venn-circle"));
     }
 
-    let circle_config = config.circle_config(coalition);
-
-    let circle = Circle::new()
-        .set("r", radius * 2.0)
-        .set("cx", cx)
-        .set("cy", cy)
+    let circle = circle
         .set("fill", "transparent")
         .set("stroke", circle_config.color.as_str())
         .set("stroke-width", 0.025);
@@ -102,52 +128,12 @@ pub(super) fn draw_circle(
     if circle_config.opacity != 1.0 {
         group = group.set("opacity", circle_config.opacity);
     }
-    group = group.add(circle);
-
-    out.add(group)
-}
-
-#[derive(Debug, Clone, Copy)]
-pub(super) enum Coalition {
-    Below,
-    Edge,
-    Above,
-}
-
-impl Coalition {
-    fn from_values(mask: &[bool], values: &[f64]) -> Self {
-        let n = mask.len();
-        debug_assert!(n == values.len());
-        let mut total: f64 = 0.0;
-        for i in 0..n {
-            if !mask[i] {
-                continue;
-            }
-            total += values[i];
-        }
-
-        let mut on_edge = true;
-        for i in 0..n {
-            if !mask[i] {
-                continue;
-            }
-            if total - values[i] >= 0.5 {
-                on_edge = false;
-            }
-        }
-
-        if total < 0.5 {
-            Self::Below
-        } else if on_edge {
-            Self::Edge
-        } else {
-            Self::Above
-        }
-    }
+    group.add(circle)
 }
 
 /// Method to decide where each pie chart should be placed.
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CirclePlacement {
     /// Each circle is placed on it's grid position.
     Basic,
@@ -158,12 +144,7 @@ pub enum CirclePlacement {
 }
 
 impl CirclePlacement {
-    pub(super) fn get_circle_pos(
-        &self,
-        x: usize,
-        y: usize,
-        internal_offset: InnerOffset,
-    ) -> (f64, f64) {
+    pub(super) fn get_circle_pos(&self, x: usize, y: usize, internal_offset: InnerOffset) -> Point {
         match self {
             Self::Basic => (x as f64 + 0.5, (y as f64) + 0.5),
             Self::SquareCenter => {
@@ -1,10 +1,35 @@
+pub use super::circles::{CircleConfig, CirclePlacement};
 pub use super::corner::CornerStyle;
-use super::{
-    Coalition,
-    circles::{CircleConfig, CirclePlacement},
+pub use super::legend::LegendPosition;
+pub use super::style::Styling;
+use crate::{
+    coalition::{Coalition, Game, Seats},
+    diagram::SetMask,
 };
 
+/// Coalitions whose values sum to at least `threshold`, see
+/// [`DiagramConfig::thresholds`] and [`Game`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct ThresholdBand {
+    /// Smallest sum of values of a coalition in the band.
+    pub threshold: f64,
+
+    /// Circular border of the pie chart.
+    pub circle: CircleConfig,
+
+    /// Circular border of the pie chart used instead of `circle`, if removing
+    /// any set from the coalition would cause the sum to be less than
+    /// `threshold`.
+    pub circle_edge: Option<CircleConfig>,
+}
+
 /// Configuration of a Venn diagram.
+///
+/// With the `serde` feature, configs are stored with a `version` field, and
+/// missing fields, including the version, are deserialized to their default
+/// value. Unknown fields and versions are rejected.
 #[derive(Debug, Clone)]
 pub struct DiagramConfig {
     /// Width of each polyomino border.
@@ -13,18 +38,14 @@ pub struct DiagramConfig {
     /// Circle radius.
     pub radius: f64,
 
-    /// Circular border of the pie chart, when sum of sets in intersection is
-    /// less than 50%.
-    pub circle_below: CircleConfig,
-
-    /// Circular border of the pie chart, when sum of sets in intersection is
-    /// more than 50%, but removing any set would cause the sum to be less than
-    /// 50%.
-    pub circle_edge: CircleConfig,
-
-    /// Circular border of the pie chart, when sum of sets in intersection is
-    /// more than 50%.
-    pub circle_above: CircleConfig,
+    /// Circular border of the pie chart, depending on the sum of sets in the
+    /// intersection. The band with the largest threshold which is at most the
+    /// sum is used, and no border is drawn if there is no such band.
+    ///
+    /// By default, the border is red when the sum is less than 50%, white when
+    /// it's at least 50% but removing any set would cause it to be less than
+    /// 50%, and green otherwise.
+    pub thresholds: Vec<ThresholdBand>,
 
     /// Method to decide where each pie chart should be placed.
     pub circle_placement: CirclePlacement,
@@ -41,13 +62,31 @@ pub struct DiagramConfig {
     /// Scale factor for the height (in pixels) of the SVG, if any.
     pub height_mul: Option<f64>,
 
-    /// Number used to set Id of the mask. Nessesary when there are multiple
-    /// of the same SVG on the same page. If not used, the SVG may use each
-    /// values from eachother, creating corrupted results.
+    /// Number used in the ids of the SVG, which must be unique when there are
+    /// multiple SVGs on the same page. If not set, the ids are derived from a
+    /// hash of everything that is drawn, so that different SVGs get different
+    /// ids. With classes, see [`Styling`], the SVG itself has the id
+    /// `venn-{id}`.
+    ///
+    /// Identical diagrams get identical ids, which draw correctly but
+    /// duplicate ids on the page. Set this if the ids are used to find the
+    /// elements of a diagram, e.g. the pie charts with ids `region_{id}_{subset}`.
     pub id: Option<usize>,
 
     /// Opacity of inside of each set in diagram
     pub fill_opacity: f64,
+
+    /// Where to draw the legend, which lists the color, name and value of
+    /// each set. The SVG grows to make room for it.
+    pub legend: LegendPosition,
+
+    /// Name of each set, shown in the legend. If empty, only the values are
+    /// shown.
+    pub names: Vec<String>,
+
+    /// Whether colors, opacities and stroke widths are set as attributes or
+    /// by a stylesheet using classes.
+    pub styling: Styling,
 }
 
 impl Default for DiagramConfig {
@@ -55,9 +94,18 @@ impl Default for DiagramConfig {
         Self {
             line_width: 0.05,
             radius: 0.175,
-            circle_below: CircleConfig::new(0.3, String::from("red")),
-            circle_edge: CircleConfig::new(1.0, String::from("white")),
-            circle_above: CircleConfig::new(0.3, String::from("green")),
+            thresholds: vec![
+                ThresholdBand {
+                    threshold: 0.0,
+                    circle: CircleConfig::new(0.3, String::from("red")),
+                    circle_edge: None,
+                },
+                ThresholdBand {
+                    threshold: 0.5,
+                    circle: CircleConfig::new(0.3, String::from("green")),
+                    circle_edge: Some(CircleConfig::new(1.0, String::from("white"))),
+                },
+            ],
             circle_placement: CirclePlacement::default(),
             corner_style: CornerStyle::Smooth,
             corner_offset: 0.15,
@@ -65,16 +113,108 @@ impl Default for DiagramConfig {
             height_mul: None,
             id: None,
             fill_opacity: 0.2,
+            legend: LegendPosition::None,
+            names: Vec::new(),
+            styling: Styling::Inline,
         }
     }
 }
 
 impl DiagramConfig {
-    pub(super) fn circle_config(&self, coalition: Coalition) -> &CircleConfig {
-        match coalition {
-            Coalition::Below => &self.circle_below,
-            Coalition::Edge => &self.circle_edge,
-            Coalition::Above => &self.circle_above,
+    // The game of each band, using seat counts instead of `values` if given
+    pub(super) fn games(&self, values: &[f64], seats: Option<&Seats>) -> Vec<Game> {
+        let game = |band: &ThresholdBand| match seats {
+            Some(seats) => seats.game(band.threshold),
+            None => Game::new(values.to_vec(), band.threshold),
+        };
+        self.thresholds.iter().map(game).collect()
+    }
+
+    // The index of the band of a coalition, and whether it's on the edge of
+    // the band, if any, using the game of each band from `games`.
+    pub(super) fn circle_band(&self, coalition: SetMask, games: &[Game]) -> Option<(usize, bool)> {
+        let (index, band) = self
+            .thresholds
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| games[*i].is_winning(coalition))
+            .max_by(|(_, a), (_, b)| a.threshold.total_cmp(&b.threshold))?;
+
+        let edge =
+            band.circle_edge.is_some() && games[index].classify(coalition) == Coalition::Edge;
+        Some((index, edge))
+    }
+
+    // The circle of band `index`, on its edge or not.
+    pub(super) fn circle_config(&self, index: usize, edge: bool) -> &CircleConfig {
+        let band = &self.thresholds[index];
+        match &band.circle_edge {
+            Some(circle) if edge => circle,
+            _ => &band.circle,
         }
     }
+
+    // The classification of a coalition by the largest threshold, if any,
+    // using the game of each band from `games`.
+    pub(super) fn coalition(&self, coalition: SetMask, games: &[Game]) -> Option<Coalition> {
+        let (index, _) = self
+            .thresholds
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.threshold.total_cmp(&b.threshold))?;
+        Some(games[index].classify(coalition))
+    }
+
+    // The name of set `i`, or its letter as in `Diagram::from_letters` if it
+    // has no name.
+    pub(super) fn set_name(&self, i: usize) -> String {
+        match self.names.get(i) {
+            Some(name) => name.clone(),
+            None if i < 26 => char::from(b'A' + i as u8).to_string(),
+            None => (i + 1).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The color of the circle of a coalition
+    fn color<'a>(config: &'a DiagramConfig, coalition: SetMask, values: &[f64]) -> Option<&'a str> {
+        let (index, edge) = config.circle_band(coalition, &config.games(values, None))?;
+        Some(config.circle_config(index, edge).color.as_str())
+    }
+
+    #[test]
+    fn default_thresholds() {
+        let config = DiagramConfig::default();
+        let values = [0.3, 0.3, 0.4];
+        assert_eq!(color(&config, 0b001, &values), Some("red"));
+        assert_eq!(color(&config, 0b011, &values), Some("white"));
+        assert_eq!(color(&config, 0b111, &values), Some("green"));
+
+        let games = config.games(&values, None);
+        assert_eq!(config.coalition(0b001, &games), Some(Coalition::Below));
+        assert_eq!(config.coalition(0b011, &games), Some(Coalition::Edge));
+        assert_eq!(config.coalition(0b111, &games), Some(Coalition::Above));
+        assert_eq!(config.set_name(1), "B");
+    }
+
+    #[test]
+    fn multiple_thresholds() {
+        let band = |threshold, color: &str| ThresholdBand {
+            threshold,
+            circle: CircleConfig::new(1.0, String::from(color)),
+            circle_edge: None,
+        };
+        let config = DiagramConfig {
+            thresholds: vec![band(2.0 / 3.0, "green"), band(0.5, "yellow")],
+            ..DiagramConfig::default()
+        };
+        let values = [0.3, 0.3, 0.4];
+        assert_eq!(color(&config, 0b001, &values), None);
+        assert_eq!(color(&config, 0b011, &values), Some("yellow"));
+        assert_eq!(color(&config, 0b101, &values), Some("green"));
+    }
 }
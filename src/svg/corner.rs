@@ -1,9 +1,9 @@
-use svg::node::element::{Path, path::Data};
-
+use super::geometry::{Cycle, Outline, Segment};
 use crate::direction::Direction;
 
 /// How each corner of a polyomino border should be drawn.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CornerStyle {
     /// Each corner consists of two 45° turns
     Straight,
@@ -110,38 +110,47 @@ pub(super) struct Corner {
 }
 
 impl Corner {
-    // Parameters which can be used to create an "elliptical_arc" in SVG
-    pub(super) fn params(&self) -> (f64, f64, i32, i32, i32, f64, f64) {
-        (self.radius, self.radius, 0, 0, i32::from(self.clockwise), self.to.0, self.to.1)
+    // The arc of a smooth corner
+    fn arc(&self) -> Segment {
+        Segment::Arc { to: self.to, radius: self.radius, clockwise: self.clockwise }
     }
 }
 
-/// Combine corners into an SVG path. Returns `None` if `corners` is empty.
-fn get_rounded_path(corners: &[Corner], corner_style: CornerStyle) -> Option<Path> {
-    corners.split_first().map(|(first, rest)| {
-        let mut data = Data::new().move_to(first.from);
+/// Combine cycles of corners into an outline. Returns `None` if there are no
+/// cycles, or if any cycle is empty.
+fn get_rounded_outline(cycles: &[Vec<Corner>], corner_style: CornerStyle) -> Option<Outline> {
+    if cycles.is_empty() {
+        return None;
+    }
+    let mut out = Vec::with_capacity(cycles.len());
+    for corners in cycles {
+        let (first, rest) = corners.split_first()?;
+        let mut segments = Vec::new();
         match corner_style {
             CornerStyle::Straight => {
-                data = data.line_to(first.to);
+                segments.push(Segment::Line { to: first.to });
                 for corner in rest {
-                    data = data.line_to(corner.from).line_to(corner.to);
+                    segments.push(Segment::Line { to: corner.from });
+                    segments.push(Segment::Line { to: corner.to });
                 }
             }
             CornerStyle::Smooth => {
-                data = data.elliptical_arc_to(first.params());
+                segments.push(first.arc());
                 for corner in rest {
-                    data = data.line_to(corner.from).elliptical_arc_to(corner.params());
+                    segments.push(Segment::Line { to: corner.from });
+                    segments.push(corner.arc());
                 }
             }
         }
-        data = data.close();
-        Path::new().set("d", data)
-    })
+        out.push(Cycle { start: first.from, segments });
+    }
+    Some(Outline { cycles: out })
 }
 
-pub(super) fn get_rounded_paths(
-    corners: &[Vec<Corner>],
+// The outline of each set, or the index of the first set without an outline
+pub(super) fn get_rounded_outlines(
+    corners: &[Vec<Vec<Corner>>],
     corner_style: CornerStyle,
-) -> Option<Vec<Path>> {
-    corners.iter().map(|v| get_rounded_path(v, corner_style)).collect()
+) -> Result<Vec<Outline>, usize> {
+    corners.iter().enumerate().map(|(i, v)| get_rounded_outline(v, corner_style).ok_or(i)).collect()
 }
@@ -0,0 +1,90 @@
+use std::{error::Error, fmt};
+
+/// Error returned when a diagram can't be rendered, see
+/// [`Layout::try_to_svg`](super::Layout::try_to_svg).
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    /// The number of values, or seat counts, isn't the number of sets.
+    WrongValueCount {
+        /// Number of values.
+        count: usize,
+        /// Number of sets.
+        expected: usize,
+    },
+
+    /// The number of colors isn't the number of sets.
+    WrongColorCount {
+        /// Number of colors.
+        count: usize,
+        /// Number of sets.
+        expected: usize,
+    },
+
+    /// A value which is negative, infinite or NaN.
+    InvalidValue {
+        /// Index of the set.
+        set: usize,
+        /// The invalid value.
+        value: f64,
+    },
+
+    /// Values which don't sum to 1.
+    InvalidSum {
+        /// Sum of the values.
+        sum: f64,
+    },
+
+    /// A set without a border in the layout.
+    EmptyPath {
+        /// Index of the set.
+        set: usize,
+    },
+
+    /// A color which isn't a name or a hex code like `#EE2020` or `#E22` of
+    /// CSS, for formats which don't support every color of CSS, such as
+    /// [`PdfRenderer`](super::PdfRenderer).
+    InvalidColor {
+        /// The invalid color.
+        color: String,
+    },
+
+    /// The size of an image in pixels, which is empty or larger than
+    /// [`PngRenderer::MAX_SIZE`](super::PngRenderer::MAX_SIZE) or
+    /// [`PngRenderer::MAX_PIXELS`](super::PngRenderer::MAX_PIXELS).
+    InvalidImageSize {
+        /// Width of the image.
+        width: u32,
+        /// Height of the image.
+        height: u32,
+    },
+
+    /// A field of [`DiagramConfig`](super::DiagramConfig) with an invalid
+    /// value, such as a radius which isn't positive.
+    InvalidConfig {
+        /// Name of the field.
+        field: &'static str,
+    },
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongValueCount { count, expected } => {
+                write!(f, "expected {expected} values, found {count}")
+            }
+            Self::WrongColorCount { count, expected } => {
+                write!(f, "expected {expected} colors, found {count}")
+            }
+            Self::InvalidValue { set, value } => write!(f, "set {set} has invalid value {value}"),
+            Self::InvalidSum { sum } => write!(f, "values sum to {sum}, expected 1"),
+            Self::EmptyPath { set } => write!(f, "set {set} has no border in the layout"),
+            Self::InvalidColor { color } => write!(f, "invalid color `{color}`"),
+            Self::InvalidImageSize { width, height } => {
+                write!(f, "image of {width}x{height} pixels is empty or too large")
+            }
+            Self::InvalidConfig { field } => write!(f, "invalid value of `{field}` in config"),
+        }
+    }
+}
+
+impl Error for RenderError {}
@@ -0,0 +1,328 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use super::{
+    CircleConfig, DiagramConfig, Layout, RenderError, corner::get_rounded_outlines, get_points,
+    inner_offset,
+};
+use crate::{
+    coalition::{Coalition, Seats},
+    diagram::SetMask,
+};
+
+/// A point in a diagram, where cell `(x, y)` covers `x..(x + 1)` and
+/// `y..(y + 1)`, so `y` grows downwards.
+pub type Point = (f64, f64);
+
+/// A part of a [`Cycle`], which starts where the previous part ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// A straight line.
+    Line {
+        /// End of the line.
+        to: Point,
+    },
+
+    /// A circular arc of at most a quarter turn.
+    Arc {
+        /// End of the arc.
+        to: Point,
+        /// Radius of the circle.
+        radius: f64,
+        /// If the arc turns clockwise, as seen with `y` growing downwards.
+        clockwise: bool,
+    },
+}
+
+/// A closed curve, which returns to `start` after the last segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    /// Start of the first segment.
+    pub start: Point,
+
+    /// Each part of the curve.
+    pub segments: Vec<Segment>,
+}
+
+// A segment of a cycle, where an arc is given by its circle and its start and
+// end angles as in `arc_angles`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Resolved {
+    Line { to: Point },
+    Arc { to: Point, center: Point, radius: f64, start: f64, end: f64 },
+}
+
+impl Cycle {
+    // The segments with arcs resolved, where arcs without a radius or length
+    // are lines
+    pub(super) fn resolved(&self) -> impl Iterator<Item = Resolved> + '_ {
+        self.segments.iter().scan(self.start, |current, segment| {
+            let from = *current;
+            let resolved = match *segment {
+                Segment::Arc { to, radius, clockwise } if radius > 0.0 && to != from => {
+                    let (center, start, end) = arc_angles(from, to, radius, clockwise);
+                    Resolved::Arc { to, center, radius, start, end }
+                }
+                Segment::Line { to } | Segment::Arc { to, .. } => Resolved::Line { to },
+            };
+            let (Resolved::Line { to } | Resolved::Arc { to, .. }) = resolved;
+            *current = to;
+            Some(resolved)
+        })
+    }
+}
+
+/// The border of a set, with one cycle for each component and each hole. The
+/// inside of the set is decided by the even-odd rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outline {
+    /// Each closed curve of the border.
+    pub cycles: Vec<Cycle>,
+}
+
+/// A slice of a [`Pie`]. Angles are in radians, clockwise from straight up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slice {
+    /// Index of the set.
+    pub set: usize,
+
+    /// Angle where the slice starts.
+    pub start: f64,
+
+    /// Angle where the slice ends.
+    pub end: f64,
+}
+
+/// The pie chart of a cell, which has one slice for each set the cell is part
+/// of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pie {
+    /// Position of the cell.
+    pub cell: (usize, usize),
+
+    /// The sets the cell is part of.
+    pub subset: SetMask,
+
+    /// Center of the pie chart.
+    pub center: Point,
+
+    /// Radius of the pie chart.
+    pub radius: f64,
+
+    /// Each slice, in the order of the sets.
+    pub slices: Vec<Slice>,
+
+    /// Classification of the subset by the largest threshold of
+    /// [`DiagramConfig::thresholds`], if any.
+    pub coalition: Option<Coalition>,
+
+    /// Index of the band of [`DiagramConfig::thresholds`] of the subset, if
+    /// any.
+    pub band: Option<usize>,
+
+    /// If the subset is on the edge of its band, so that the circle is
+    /// `circle_edge` of the band.
+    pub edge: bool,
+}
+
+impl Pie {
+    /// The circle around the pie chart, if any.
+    #[must_use]
+    pub fn circle<'a>(&self, config: &'a DiagramConfig) -> Option<&'a CircleConfig> {
+        Some(config.circle_config(self.band?, self.edge))
+    }
+}
+
+/// The geometry of a diagram, independent of the output format, see
+/// [`Scene`](super::Scene).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geometry {
+    /// Bounding box of the diagram as `(x, y, width, height)`, with margins
+    /// of half a cell.
+    pub bounds: (f64, f64, f64, f64),
+
+    /// The outline of each set.
+    pub outlines: Vec<Outline>,
+
+    /// The pie chart of each cell which is part of any set, column by column.
+    pub pies: Vec<Pie>,
+}
+
+// The center of an arc of at most half a turn from `from` to `to`, and its
+// start and end angles in radians. Angles grow clockwise since `y` grows
+// downwards.
+pub(super) fn arc_angles(
+    from: Point,
+    to: Point,
+    radius: f64,
+    clockwise: bool,
+) -> (Point, f64, f64) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    let (mx, my) = (f64::midpoint(from.0, to.0), f64::midpoint(from.1, to.1));
+    let distance = (radius * radius - length * length / 4.0).max(0.0).sqrt();
+    // The center is to the right of the chord when turning clockwise
+    let side = if clockwise { 1.0 } else { -1.0 };
+    let (cx, cy) = (mx - side * distance * dy / length, my + side * distance * dx / length);
+
+    let start = (from.1 - cy).atan2(from.0 - cx);
+    let end = (to.1 - cy).atan2(to.0 - cx);
+    let end = if clockwise {
+        start + (end - start).rem_euclid(TAU)
+    } else {
+        start - (start - end).rem_euclid(TAU)
+    };
+    ((cx, cy), start, end)
+}
+
+// Cubic Bézier curves approximating the arc around `center` from angle `start`
+// to `end`, as the two control points and the end of each curve, with at most
+// a quarter turn per curve.
+pub(super) fn arc_curves(center: Point, radius: f64, start: f64, end: f64) -> Vec<[Point; 3]> {
+    let at = |angle: f64| (center.0 + radius * angle.cos(), center.1 + radius * angle.sin());
+    let count = ((end - start).abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = (end - start) / count as f64;
+    let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+    (0..count)
+        .map(|i| {
+            let a = start + i as f64 * step;
+            let b = a + step;
+            let (from, to) = (at(a), at(b));
+            [
+                (from.0 - k * a.sin(), from.1 + k * a.cos()),
+                (to.0 + k * b.sin(), to.1 - k * b.cos()),
+                to,
+            ]
+        })
+        .collect()
+}
+
+impl Layout {
+    // Compute the geometry, using the seat counts instead of `values` to
+    // classify coalitions if given
+    pub(super) fn geometry(
+        &self,
+        values: &[f64],
+        seats: Option<&Seats>,
+        config: &DiagramConfig,
+    ) -> Result<Geometry, RenderError> {
+        let Self { width, height, combined_paths, offsets, diagram } = self;
+        let internal_offsets =
+            inner_offset(*width, *height, offsets, combined_paths, config.line_width);
+
+        let points = get_points(
+            *width,
+            *height,
+            combined_paths,
+            offsets,
+            config.line_width,
+            config.corner_offset,
+        );
+
+        let outlines = get_rounded_outlines(&points, config.corner_style)
+            .map_err(|set| RenderError::EmptyPath { set })?;
+
+        let games = config.games(values, seats);
+        let mut pies = Vec::new();
+        for x in 0..*width {
+            for y in 0..*height {
+                let Some(subset) = diagram.subset_at(x, y) else {
+                    continue;
+                };
+                let center = config.circle_placement.get_circle_pos(x, y, internal_offsets[(x, y)]);
+
+                let mut added = 0.0;
+                let mut slices = Vec::new();
+                for set in (0..self.n()).filter(|i| subset & (1 << i) != 0) {
+                    let end = added + values[set];
+                    // Sometimes the end is slightly larger than a full turn
+                    let angle = |x: f64| (TAU * x).clamp(0.0, TAU);
+                    slices.push(Slice { set, start: angle(added), end: angle(end) });
+                    added = end;
+                }
+
+                let band = config.circle_band(subset, &games);
+                pies.push(Pie {
+                    cell: (x, y),
+                    subset,
+                    center,
+                    radius: 2.0 * config.radius,
+                    slices,
+                    coalition: config.coalition(subset, &games),
+                    band: band.map(|(band, _)| band),
+                    edge: band.is_some_and(|(_, edge)| edge),
+                });
+            }
+        }
+
+        Ok(Geometry {
+            bounds: (-0.5, -0.5, (width + 1) as f64, (height + 1) as f64),
+            outlines,
+            pies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::d3::LAYOUT_OPTIMIZED_THREE;
+
+    #[test]
+    fn pies_and_outlines() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let config = DiagramConfig::default();
+        let values = [0.25, 0.25, 0.5];
+        let geometry = layout.geometry(&values, None, &config).unwrap();
+
+        assert_eq!(geometry.outlines.len(), 3);
+        for pie in &geometry.pies {
+            assert_eq!(pie.radius, 2.0 * config.radius);
+            let sets = pie.slices.iter().fold(0, |acc, slice| acc | (1 << slice.set));
+            assert_eq!(sets, pie.subset);
+            let angle: f64 = pie.slices.iter().map(|slice| slice.end - slice.start).sum();
+            let total: f64 = pie.slices.iter().map(|slice| values[slice.set]).sum();
+            assert!((angle - TAU * total).abs() < 1e-9);
+        }
+        let full = geometry.pies.iter().find(|pie| pie.subset == 0b111).unwrap();
+        assert_eq!(full.slices[2].start, TAU / 2.0);
+        assert_eq!(full.coalition, Some(Coalition::Above));
+    }
+
+    #[test]
+    fn arcs() {
+        // A quarter turn clockwise from the top of a circle around the origin
+        let ((cx, cy), start, end) = arc_angles((0.0, -1.0), (1.0, 0.0), 1.0, true);
+        assert!(cx.abs() < 1e-9 && cy.abs() < 1e-9);
+        assert!((start + FRAC_PI_2).abs() < 1e-9 && end.abs() < 1e-9);
+        let (_, start, end) = arc_angles((1.0, 0.0), (0.0, -1.0), 1.0, false);
+        assert!(start.abs() < 1e-9 && (end + FRAC_PI_2).abs() < 1e-9);
+
+        // Arcs without a radius are lines, and each arc starts where the
+        // previous segment ended
+        let cycle = Cycle {
+            start: (0.0, -1.0),
+            segments: vec![
+                Segment::Arc { to: (1.0, 0.0), radius: 1.0, clockwise: true },
+                Segment::Arc { to: (1.0, 1.0), radius: 0.0, clockwise: true },
+                Segment::Arc { to: (0.0, 2.0), radius: 1.0, clockwise: true },
+            ],
+        };
+        let resolved: Vec<_> = cycle.resolved().collect();
+        let (center, start, end) = arc_angles((1.0, 1.0), (0.0, 2.0), 1.0, true);
+        assert_eq!(resolved[1], Resolved::Line { to: (1.0, 1.0) });
+        assert_eq!(resolved[2], Resolved::Arc { to: (0.0, 2.0), center, radius: 1.0, start, end });
+
+        // The curves of a full turn end on the circle, and their middles are
+        // close to it
+        let curves = arc_curves((1.0, 2.0), 3.0, 0.0, TAU);
+        assert_eq!(curves.len(), 4);
+        let mut from = (4.0, 2.0);
+        for [a, b, to] in curves {
+            let middle = |x: fn(Point) -> f64| 0.125 * (x(from) + x(to)) + 0.375 * (x(a) + x(b));
+            let distance = |(x, y): Point| (x - 1.0).hypot(y - 2.0);
+            assert!((distance(to) - 3.0).abs() < 1e-9);
+            assert!((distance((middle(|p| p.0), middle(|p| p.1))) - 3.0).abs() < 1e-3);
+            from = to;
+        }
+    }
+}
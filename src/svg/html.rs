@@ -0,0 +1,140 @@
+use svg::node::element::SVG;
+
+use super::{DiagramConfig, Layout, Styling};
+
+// Highlighted borders are twice as wide. Every selector starts with SCOPE, and
+// is more specific than the rules of the embedded stylesheet.
+const STYLE: &str = "
+SCOPE .venn-set, SCOPE .venn-region { transition: opacity 0.15s; }
+SCOPE .venn-set.venn-dimmed, SCOPE .venn-region.venn-dimmed { opacity: 0.15; }
+SCOPE .venn-border.venn-active { stroke-width: LINE_WIDTH; }
+";
+
+// Hovering a set, i.e. its inside, border or color in the legend, highlights
+// everything drawn for it and the pie charts of the subsets containing it.
+// Hovering a pie chart highlights it and the sets in it.
+const SCRIPT: &str = "
+(() => {
+  const svg = document.currentScript.previousElementSibling;
+  const setOf = (el) => Number(/venn-set-(\\d+)/.exec(el.getAttribute('class'))[1]);
+  const items = [...svg.querySelectorAll('.venn-set, .venn-region')];
+  const isRegion = (el) => el.classList.contains('venn-region');
+  const show = (mask, region) => {
+    for (const el of items) {
+      const on = !isRegion(el)
+        ? (mask & (1 << setOf(el))) !== 0
+        : region === null ? (Number(el.dataset.subset) & mask) !== 0 : el === region;
+      el.classList.toggle('venn-dimmed', !on);
+      el.classList.toggle('venn-active', on && !isRegion(el));
+    }
+  };
+  const clear = () => items.forEach((el) => el.classList.remove('venn-dimmed', 'venn-active'));
+  for (const el of items) {
+    if (isRegion(el)) {
+      el.addEventListener('mouseenter', () => show(Number(el.dataset.subset), el));
+    } else if (!el.classList.contains('venn-piece')) {
+      el.addEventListener('mouseenter', () => show(1 << setOf(el), null));
+    } else {
+      continue;
+    }
+    el.addEventListener('mouseleave', clear);
+  }
+})();
+";
+
+/// Wrap an SVG in a self-contained HTML fragment, with a script which
+/// highlights a set and dims the rest when hovering its polyomino or its color
+/// in the legend, and highlights the borders of the sets of a pie chart when
+/// hovering it.
+///
+/// The SVG must be rendered with classes, see [`Styling`], and `config` should
+/// be the config it was rendered with.
+#[must_use]
+pub fn html(svg: &SVG, config: &DiagramConfig) -> String {
+    // The style only applies to this SVG, unless it has no id
+    let scope = match svg.get_attributes().get("id") {
+        Some(id) => format!("#{id}"),
+        None => String::from(".venn-html"),
+    };
+    let style = STYLE
+        .replace("SCOPE", &scope)
+        .replace("LINE_WIDTH", &(2.0 * config.line_width).to_string());
+    format!(
+        "<div class=\"venn-html\">\n<style>{style}</style>\n{svg}\n<script>{SCRIPT}</script>\n</div>\n"
+    )
+}
+
+impl Layout {
+    /// Render as an interactive HTML fragment, see [`html`] and
+    /// [`Layout::to_svg`]. With [`Styling::Inline`], the stylesheet is
+    /// embedded instead.
+    ///
+    /// ```
+    /// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+    /// use venn_diagrams::svg::{DiagramConfig, Layout};
+    ///
+    /// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+    /// let values = &[0.3, 0.3, 0.4];
+    /// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+    /// let html = layout.to_html(values, colors, &DiagramConfig::default());
+    /// assert!(html.starts_with("<div class=\"venn-html\">"));
+    /// ```
+    #[must_use]
+    pub fn to_html(&self, values: &[f64], colors: &[&str], config: &DiagramConfig) -> String {
+        let mut config = config.clone();
+        if config.styling == Styling::Inline {
+            config.styling = Styling::Stylesheet;
+        }
+        html(&self.to_svg(values, colors, &config), &config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::d3::LAYOUT_OPTIMIZED_THREE;
+
+    #[test]
+    fn uses_classes() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let config = DiagramConfig::default();
+        let html = layout.to_html(&[0.3, 0.3, 0.4], &["red", "green", "blue"], &config);
+        assert!(html.contains(" .venn-set-0 { fill: red; stroke: red; }"));
+        assert!(html.contains("class=\"venn-set venn-set-2 venn-border\""));
+        assert!(html.contains("stroke-width: 0.1;"));
+        assert!(html.trim_end().ends_with("</script>\n</div>"));
+    }
+
+    #[test]
+    fn scoped_styles() {
+        // Two diagrams on one page, which only differ in their styles
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let values = [0.3, 0.3, 0.4];
+        let first = layout.to_html(&values, &["red", "green", "blue"], &DiagramConfig::default());
+        let config = DiagramConfig { line_width: 0.2, fill_opacity: 0.5, ..Default::default() };
+        let second = layout.to_html(&values, &["blue", "red", "green"], &config);
+
+        let scope = |html: &str| {
+            let start = html.find("<svg id=\"").unwrap() + "<svg id=\"".len();
+            format!("#{}", &html[start..start + html[start..].find('"').unwrap()])
+        };
+        let (first_scope, second_scope) = (scope(&first), scope(&second));
+        assert_ne!(first_scope, second_scope);
+
+        // Every selector of every rule only matches inside its own diagram
+        for (html, scope) in [(&first, &first_scope), (&second, &second_scope)] {
+            let mut rules = 0;
+            for style in html.split("<style>").skip(1) {
+                let style = &style[..style.find("</style>").unwrap()];
+                for rule in style.lines().filter(|line| !line.trim().is_empty()) {
+                    let selectors = &rule[..rule.find('{').unwrap()];
+                    for selector in selectors.split(',') {
+                        assert!(selector.trim().starts_with(&format!("{scope} ")), "{rule}");
+                    }
+                    rules += 1;
+                }
+            }
+            assert!(rules > 10);
+        }
+    }
+}
@@ -1,16 +1,20 @@
-use svg::{
-    Document,
-    node::element::{Definitions, Mask, Rectangle, SVG},
-};
+use svg::node::element::SVG;
 
 use crate::{
+    coalition::Seats,
     diagram::{Diagram, DiagramConst},
     direction::DirectedEdge,
-    svg::{DiagramConfig, draw_circle, get_points, get_rounded_paths, inner_offset},
+    svg::{DiagramConfig, RenderError, Renderer, Scene, SvgRenderer, cycles},
 };
 
+// How far from 1 the sum of the values may be in `Layout::try_to_svg`
+const SUM_TOLERANCE: f64 = 1e-6;
+
 /// A Venn diagram with a computed layout of each polyomino border. For the
 /// static version, see [`LayoutConst`].
+///
+/// The border of a polyomino consists of one cycle for each component and one
+/// for each hole.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Layout {
     pub(crate) width: usize,
@@ -131,100 +135,331 @@ impl Layout {
         self.combined_paths.len()
     }
 
-    /// Render as an SVG.
+    // Move every point with `point` and every offset with `offset`. A
+    // reflection reverses the direction of each cycle, which we undo so that
+    // the inside of each polyomino is still to the right of its border.
+    fn map_edges(
+        &self,
+        diagram: Diagram,
+        point: impl Fn((usize, usize)) -> (usize, usize),
+        offset: impl Fn(&DirectedEdge, i32) -> i32,
+        reflect: bool,
+    ) -> Self {
+        let mut combined_paths = Vec::with_capacity(self.n());
+        let mut offsets = Vec::with_capacity(self.n());
+        for (path, path_offsets) in self.combined_paths.iter().zip(&self.offsets) {
+            let mut new_path = Vec::with_capacity(path.len());
+            let mut new_offsets = Vec::with_capacity(path.len());
+            for cycle in cycles(path) {
+                let mut edges: Vec<DirectedEdge> =
+                    path[cycle.clone()].iter().map(|edge| edge.map_endpoints(&point)).collect();
+                let mut cycle_offsets: Vec<i32> = path[cycle.clone()]
+                    .iter()
+                    .zip(&path_offsets[cycle])
+                    .map(|(edge, o)| offset(edge, *o))
+                    .collect();
+                if reflect {
+                    edges = edges.iter().rev().map(DirectedEdge::reversed).collect();
+                    cycle_offsets.reverse();
+                }
+                new_path.extend(edges);
+                new_offsets.extend(cycle_offsets);
+            }
+            combined_paths.push(new_path);
+            offsets.push(new_offsets);
+        }
+
+        Self { width: diagram.width(), height: diagram.height(), combined_paths, offsets, diagram }
+    }
+
+    /// Reorder the sets, so that set `i` of the new layout is set
+    /// `permutation[i]` of this layout. Combine with
+    /// [`Diagram::best_permutation`] to decide which set is drawn by which
+    /// polyomino.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutation` isn't a permutation of `0..n`.
     #[must_use]
-    pub fn to_svg(&self, values: &[f64], colors: &[&str], config: &DiagramConfig) -> SVG {
-        let Self { width, height, combined_paths, offsets, diagram: polyominoes } = self;
-        let internal_offsets =
-            inner_offset(*width, *height, offsets, combined_paths, config.line_width);
-
-        let points = get_points(
-            *width,
-            *height,
-            combined_paths,
-            offsets,
-            config.line_width,
-            config.corner_offset,
-        );
+    pub fn permute_sets(&self, permutation: &[usize]) -> Self {
+        let diagram = self.diagram.permute_sets(permutation);
+        let combined_paths = permutation.iter().map(|&i| self.combined_paths[i].clone()).collect();
+        let offsets = permutation.iter().map(|&i| self.offsets[i].clone()).collect();
+        Self { width: self.width, height: self.height, combined_paths, offsets, diagram }
+    }
 
-        let paths = get_rounded_paths(&points, config.corner_style).unwrap();
+    /// Rotate the layout 90° clockwise, keeping the position of every edge.
+    #[must_use]
+    pub fn rotate90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
 
-        // Then we create the svg
-        let min_x = -0.5;
-        let total_width = (width + 1) as f64;
+    /// Mirror the layout, so that the leftmost column becomes the rightmost.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.map_edges(
+            self.diagram.flip_horizontal(),
+            |(x, y)| (width - x, y),
+            |edge, offset| match edge {
+                DirectedEdge::Horizontal { .. } => offset,
+                DirectedEdge::Vertical { .. } => -offset,
+            },
+            true,
+        )
+    }
 
-        let min_y = -0.5;
-        let total_height = (height + 1) as f64;
+    /// Mirror the layout, so that the top row becomes the bottom row.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.map_edges(
+            self.diagram.flip_vertical(),
+            |(x, y)| (x, height - y),
+            |edge, offset| match edge {
+                DirectedEdge::Horizontal { .. } => -offset,
+                DirectedEdge::Vertical { .. } => offset,
+            },
+            true,
+        )
+    }
 
-        let mut out = Document::new().set("viewBox", (min_x, min_y, total_width, total_height));
+    /// Mirror the layout along its diagonal, so that rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.map_edges(self.diagram.transpose(), |(x, y)| (y, x), |_, offset| offset, true)
+    }
 
-        if let Some(width_mul) = config.width_mul {
-            out = out.set("width", format!("{}px", width_mul * total_width));
-        }
+    /// Render as an SVG, where set `i` has the color `colors[i]` and the
+    /// share `values[i]` of the total, so the values should sum to at most 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer values or colors than sets, see
+    /// [`Layout::try_to_svg`] for a version which validates its input.
+    #[must_use]
+    pub fn to_svg(&self, values: &[f64], colors: &[&str], config: &DiagramConfig) -> SVG {
+        SvgRenderer.render(&self.scene_unchecked(values.to_vec(), None, colors, config).unwrap())
+    }
 
-        if let Some(height_mul) = config.height_mul {
-            out = out.set("height", format!("{}px", height_mul * total_width));
-        }
+    /// Render as an SVG like [`Layout::to_svg`], but return an error instead
+    /// of drawing a broken diagram if the input is invalid, i.e. if there
+    /// isn't one value and one color for each set, if a value is negative or
+    /// not finite, if the values don't sum to 1, or if the radius or line
+    /// width of `config` isn't positive.
+    ///
+    /// ```
+    /// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+    /// use venn_diagrams::svg::{DiagramConfig, Layout, RenderError};
+    ///
+    /// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+    /// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+    /// let config = DiagramConfig::default();
+    /// assert!(layout.try_to_svg(&[0.3, 0.3, 0.4], colors, &config).is_ok());
+    /// let error = layout.try_to_svg(&[3.0, 3.0, 4.0], colors, &config).unwrap_err();
+    /// assert_eq!(error, RenderError::InvalidSum { sum: 10.0 });
+    /// ```
+    pub fn try_to_svg(
+        &self,
+        values: &[f64],
+        colors: &[&str],
+        config: &DiagramConfig,
+    ) -> Result<SVG, RenderError> {
+        Ok(SvgRenderer.render(&self.scene(values, colors, config)?))
+    }
 
-        let mask_id = match config.id {
-            Some(id) => &format!("background_mask_{id}"),
-            None => "background_mask",
-        };
+    /// Render as an SVG, where set `i` has the color `colors[i]` and
+    /// `seats.counts()[i]` seats.
+    ///
+    /// Whether a coalition reaches a threshold is decided using the seat
+    /// counts, see [`Seats::game`], and each pie chart has a tooltip with the
+    /// number of seats of the coalition.
+    ///
+    /// ```
+    /// use venn_diagrams::{coalition::Seats, constants::d3::LAYOUT_OPTIMIZED_THREE};
+    /// use venn_diagrams::svg::{DiagramConfig, Layout};
+    ///
+    /// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+    /// let seats = Seats::new(vec![107, 73, 68]);
+    /// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+    /// let svg = layout.to_svg_seats(&seats, colors, &DiagramConfig::default());
+    /// assert!(svg.to_string().contains("<title>A, B: 180 of 248 seats</title>"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer seat counts or colors than sets, see
+    /// [`Layout::try_to_svg_seats`] for a version which validates its input.
+    #[must_use]
+    pub fn to_svg_seats(&self, seats: &Seats, colors: &[&str], config: &DiagramConfig) -> SVG {
+        assert_eq!(seats.counts().len(), self.n(), "Wrong number of seat counts");
+        let scene = self.scene_unchecked(seats.fractions(), Some(seats), colors, config);
+        SvgRenderer.render(&scene.unwrap())
+    }
 
-        let mut mask = Mask::new().set("id", mask_id);
+    /// Render as an SVG like [`Layout::to_svg_seats`], but return an error
+    /// instead of panicking or drawing a broken diagram if the input is
+    /// invalid, see [`Layout::try_to_svg`].
+    pub fn try_to_svg_seats(
+        &self,
+        seats: &Seats,
+        colors: &[&str],
+        config: &DiagramConfig,
+    ) -> Result<SVG, RenderError> {
+        Ok(SvgRenderer.render(&self.scene_seats(seats, colors, config)?))
+    }
 
-        for path in &paths {
-            let part = path.clone().set("fill", "white").set("stroke", "none");
-            mask = mask.add(part);
+    /// Compute everything needed to draw the diagram with a [`Renderer`],
+    /// where set `i` has the color `colors[i]` and the share `values[i]` of
+    /// the total.
+    ///
+    /// Returns the same errors as [`Layout::try_to_svg`].
+    pub fn scene<'a>(
+        &'a self,
+        values: &[f64],
+        colors: &'a [&'a str],
+        config: &'a DiagramConfig,
+    ) -> Result<Scene<'a>, RenderError> {
+        let n = self.n();
+        if values.len() != n {
+            return Err(RenderError::WrongValueCount { count: values.len(), expected: n });
+        }
+        if let Some((set, &value)) =
+            values.iter().enumerate().find(|(_, value)| !(value.is_finite() && **value >= 0.0))
+        {
+            return Err(RenderError::InvalidValue { set, value });
+        }
+        let sum: f64 = values.iter().sum();
+        if (sum - 1.0).abs() > SUM_TOLERANCE {
+            return Err(RenderError::InvalidSum { sum });
         }
+        self.check_input(colors, config)?;
+        self.scene_unchecked(values.to_vec(), None, colors, config)
+    }
 
-        out = out.add(Definitions::new().add(mask));
+    /// Compute everything needed to draw the diagram with a [`Renderer`],
+    /// where set `i` has the color `colors[i]` and `seats.counts()[i]` seats,
+    /// see [`Layout::to_svg_seats`].
+    ///
+    /// Returns the same errors as [`Layout::try_to_svg_seats`].
+    pub fn scene_seats<'a>(
+        &'a self,
+        seats: &'a Seats,
+        colors: &'a [&'a str],
+        config: &'a DiagramConfig,
+    ) -> Result<Scene<'a>, RenderError> {
+        let count = seats.counts().len();
+        if count != self.n() {
+            return Err(RenderError::WrongValueCount { count, expected: self.n() });
+        }
+        self.check_input(colors, config)?;
+        self.scene_unchecked(seats.fractions(), Some(seats), colors, config)
+    }
 
-        let rect = Rectangle::new()
-            .set("width", total_width)
-            .set("height", total_height)
-            .set("x", min_x)
-            .set("y", min_y)
-            .set("mask", format!("url(#{mask_id})"));
+    // Check the input of a scene, except for the values
+    fn check_input(&self, colors: &[&str], config: &DiagramConfig) -> Result<(), RenderError> {
+        if colors.len() != self.n() {
+            return Err(RenderError::WrongColorCount { count: colors.len(), expected: self.n() });
+        }
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+        if !positive(config.radius) {
+            return Err(RenderError::InvalidConfig { field: "radius" });
+        }
+        if !positive(config.line_width) {
+            return Err(RenderError::InvalidConfig { field: "line_width" });
+        }
+        Ok(())
+    }
 
-        out = out.add(rect);
+    fn scene_unchecked<'a>(
+        &'a self,
+        values: Vec<f64>,
+        seats: Option<&'a Seats>,
+        colors: &'a [&'a str],
+        config: &'a DiagramConfig,
+    ) -> Result<Scene<'a>, RenderError> {
+        let geometry = self.geometry(&values, seats, config)?;
+        Ok(Scene { layout: self, geometry, values, seats, colors, config })
+    }
+}
 
-        for (path, color) in paths.iter().zip(colors) {
-            let path = path
-                .clone()
-                .set("fill", *color)
-                .set("fill-opacity", config.fill_opacity)
-                .set("stroke", "none");
-            out = out.add(path);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::d3::LAYOUT_OPTIMIZED_THREE, svg::Styling};
+
+    #[test]
+    fn unique_ids() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let colors = ["red", "green", "blue"];
+        let config = DiagramConfig::default();
+        let mask_id = |values: &[f64], config: &DiagramConfig| {
+            let svg = layout.to_svg(values, &colors, config).to_string();
+            let start = svg.find("<mask id=\"").unwrap() + 10;
+            svg[start..].split('"').next().unwrap().to_string()
+        };
 
-        for (path, color) in paths.iter().zip(colors) {
-            let path = path
-                .clone()
-                .set("fill", "none")
-                .set("stroke", *color)
-                .set("stroke-width", config.line_width);
-            out = out.add(path);
-        }
+        let id = mask_id(&[0.3, 0.3, 0.4], &config);
+        assert_eq!(id, mask_id(&[0.3, 0.3, 0.4], &config));
+        assert_ne!(id, mask_id(&[0.4, 0.3, 0.3], &config));
+        assert!(id.starts_with("background_mask_"));
 
-        // Draw the pie charts
-        let mut pairs = vec![false; self.n()];
-        for x in 0..*width {
-            for y in 0..*height {
-                let mut any_true = false;
-                for i in 0..self.n() {
-                    let v = polyominoes.polyominos[i][(x, y)];
-                    any_true |= v;
-                    pairs[i] = v;
-                }
-                if any_true {
-                    let (x_pos, y_pos) =
-                        config.circle_placement.get_circle_pos(x, y, internal_offsets[(x, y)]);
-                    out = draw_circle(x_pos, y_pos, &pairs, out, config, values, colors);
-                }
-            }
-        }
+        // The stylesheet is scoped by the same id
+        let config = DiagramConfig { styling: Styling::Stylesheet, ..config };
+        let svg = layout.to_svg(&[0.3, 0.3, 0.4], &colors, &config).to_string();
+        let scope = mask_id(&[0.3, 0.3, 0.4], &config).replace("background_mask_", "venn-");
+        assert!(svg.starts_with(&format!("<svg id=\"{scope}\"")));
+        assert!(svg.contains(&format!("#{scope} .venn-set-0 {{ fill: red;")));
+
+        let config = DiagramConfig { id: Some(3), ..config };
+        assert_eq!(mask_id(&[0.3, 0.3, 0.4], &config), "background_mask_3");
+    }
+
+    #[test]
+    fn invalid_input() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let colors = ["red", "green", "blue"];
+        let config = DiagramConfig::default();
+        let render = |values: &[f64], colors: &[&str], config: &DiagramConfig| {
+            layout.try_to_svg(values, colors, config).map(|_| ())
+        };
 
-        out
+        assert_eq!(render(&[0.3, 0.3, 0.4], &colors, &config), Ok(()));
+        assert_eq!(
+            render(&[0.5, 0.5], &colors, &config),
+            Err(RenderError::WrongValueCount { count: 2, expected: 3 })
+        );
+        assert_eq!(
+            render(&[0.3, 0.3, 0.4], &colors[0..2], &config),
+            Err(RenderError::WrongColorCount { count: 2, expected: 3 })
+        );
+        assert_eq!(
+            render(&[0.3, 0.8, -0.1], &colors, &config),
+            Err(RenderError::InvalidValue { set: 2, value: -0.1 })
+        );
+        assert!(matches!(
+            render(&[0.3, f64::NAN, 0.4], &colors, &config),
+            Err(RenderError::InvalidValue { set: 1, .. })
+        ));
+        assert_eq!(
+            render(&[0.3, 0.3, 0.3], &colors, &config),
+            Err(RenderError::InvalidSum { sum: 0.3 + 0.3 + 0.3 })
+        );
+        let zero_radius = DiagramConfig { radius: 0.0, ..DiagramConfig::default() };
+        assert_eq!(
+            render(&[0.3, 0.3, 0.4], &colors, &zero_radius),
+            Err(RenderError::InvalidConfig { field: "radius" })
+        );
+        let nan_width = DiagramConfig { line_width: f64::NAN, ..DiagramConfig::default() };
+        assert_eq!(
+            render(&[0.3, 0.3, 0.4], &colors, &nan_width),
+            Err(RenderError::InvalidConfig { field: "line_width" })
+        );
+
+        let seats = Seats::new(vec![1, 2]);
+        assert_eq!(
+            layout.try_to_svg_seats(&seats, &colors, &config).map(|_| ()),
+            Err(RenderError::WrongValueCount { count: 2, expected: 3 })
+        );
     }
 }
@@ -0,0 +1,170 @@
+use svg::node::element::{Group, Rectangle, Text};
+
+use super::{Point, Scene, style::Styling};
+
+// Sizes in the same unit as the cells of the diagram
+pub(super) const FONT_SIZE: f64 = 0.4;
+// Rough width of a character, since we can't measure the text
+const CHAR_WIDTH: f64 = 0.6 * FONT_SIZE;
+pub(super) const ROW_HEIGHT: f64 = 0.6;
+pub(super) const SWATCH: f64 = 0.4;
+pub(super) const SPACING: f64 = 0.2;
+const MARGIN: f64 = 0.5;
+
+/// Where to draw the legend of a diagram, see [`DiagramConfig`].
+///
+/// [`DiagramConfig`]: super::DiagramConfig
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LegendPosition {
+    /// No legend.
+    #[default]
+    None,
+
+    /// To the right of the diagram, with one set per row.
+    Right,
+
+    /// Below the diagram, with as many sets per row as fit in its width.
+    Bottom,
+}
+
+// The color and text of each set in the legend of a scene
+pub(super) fn legend_entries<'a>(scene: &Scene<'a>) -> Vec<(&'a str, String)> {
+    let Scene { layout, values, seats, colors, config, .. } = scene;
+    (0..layout.n())
+        .map(|i| {
+            let value = match seats {
+                Some(seats) => seats.counts()[i].to_string(),
+                None => format!("{:.1}%", 100.0 * values[i]),
+            };
+            let text = match config.names.get(i) {
+                Some(name) => format!("{name} {value}"),
+                None => value,
+            };
+            (colors[i], text)
+        })
+        .collect()
+}
+
+// The top left corner of each entry of a legend, and how much it grows the
+// diagram
+pub(super) struct Placement {
+    pub(super) positions: Vec<Point>,
+    pub(super) width: f64,
+    pub(super) height: f64,
+}
+
+// Place a legend with a color swatch and a text for each set, next to a
+// diagram with the given bounding box.
+pub(super) fn place_legend(
+    entries: &[(&str, String)],
+    position: LegendPosition,
+    (min_x, min_y, width, height): (f64, f64, f64, f64),
+) -> Option<Placement> {
+    if position == LegendPosition::None || entries.is_empty() {
+        return None;
+    }
+
+    let max_chars = entries.iter().map(|(_, text)| text.chars().count()).max().unwrap_or(0);
+    let entry_width = SWATCH + SPACING + max_chars as f64 * CHAR_WIDTH + 2.0 * SPACING;
+
+    let (x0, y0, columns) = match position {
+        LegendPosition::Right => (min_x + width, min_y + MARGIN, 1),
+        _ => {
+            let columns = ((width - MARGIN) / entry_width).floor().max(1.0) as usize;
+            (min_x + MARGIN, min_y + height, columns)
+        }
+    };
+    let rows = entries.len().div_ceil(columns);
+
+    let positions = (0..entries.len())
+        .map(|i| (x0 + (i % columns) as f64 * entry_width, y0 + (i / columns) as f64 * ROW_HEIGHT))
+        .collect();
+
+    let (legend_width, legend_height) =
+        (columns as f64 * entry_width + MARGIN, rows as f64 * ROW_HEIGHT + MARGIN);
+    let (width, height) = match position {
+        LegendPosition::Right => (legend_width, (legend_height + MARGIN - height).max(0.0)),
+        _ => (0.0, legend_height),
+    };
+    Some(Placement { positions, width, height })
+}
+
+// A legend, and how much it grows the diagram
+pub(super) struct Legend {
+    pub(super) group: Group,
+    pub(super) width: f64,
+    pub(super) height: f64,
+}
+
+// Draw a legend next to a diagram with the given bounding box, see
+// `place_legend`.
+pub(super) fn draw_legend(
+    entries: &[(&str, String)],
+    position: LegendPosition,
+    styling: Styling,
+    bounds: (f64, f64, f64, f64),
+) -> Option<Legend> {
+    let Placement { positions, width, height } = place_legend(entries, position, bounds)?;
+
+    let mut group = Group::new();
+    for (i, ((color, text), (x, y))) in entries.iter().zip(positions).enumerate() {
+        let swatch = Rectangle::new()
+            .set("x", x)
+            .set("y", y + (ROW_HEIGHT - SWATCH) / 2.0)
+            .set("width", SWATCH)
+            .set("height", SWATCH);
+        let text =
+            Text::new(text.as_str()).set("x", x + SWATCH + SPACING).set("y", y + ROW_HEIGHT / 2.0);
+        let (swatch, text) = if styling.uses_classes() {
+            (
+                swatch.set("class", format!("venn-set venn-set-{i} venn-swatch")),
+                text.set("class", "venn-legend-text"),
+            )
+        } else {
+            (
+                swatch.set("fill", *color),
+                text.set("font-size", FONT_SIZE)
+                    .set("font-family", "sans-serif")
+                    .set("dominant-baseline", "central"),
+            )
+        };
+        group = group.add(swatch).add(text);
+    }
+
+    Some(Legend { group, width, height })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<(&'static str, String)> {
+        let entries = [("red", "Red 50.0%"), ("blue", "Blue 30.0%"), ("green", "Green 20.0%")];
+        entries.into_iter().map(|(color, text)| (color, String::from(text))).collect()
+    }
+
+    #[test]
+    fn size() {
+        let bounds = (-0.5, -0.5, 8.0, 3.0);
+        assert!(draw_legend(&entries(), LegendPosition::None, Styling::Inline, bounds).is_none());
+
+        let right =
+            draw_legend(&entries(), LegendPosition::Right, Styling::Inline, bounds).unwrap();
+        assert!(right.width > 0.0);
+        assert_eq!(right.height, 0.0);
+
+        // Two entries fit on each row
+        let bottom =
+            draw_legend(&entries(), LegendPosition::Bottom, Styling::Inline, bounds).unwrap();
+        assert_eq!(bottom.width, 0.0);
+        assert_eq!(bottom.height, 2.0 * ROW_HEIGHT + MARGIN);
+
+        // A narrow diagram gets a tall legend on the right
+        let right =
+            draw_legend(&entries(), LegendPosition::Right, Styling::Inline, (-0.5, -0.5, 2.0, 1.0))
+                .unwrap();
+        assert!(right.height > 0.0);
+    }
+}
@@ -1,164 +1,180 @@
 //! Drawing Venn diagrams as SVGs.
 
 mod circles;
+mod color;
 mod config;
 mod corner;
+mod error;
+mod geometry;
+mod html;
+mod legend;
 mod offset;
+mod pdf;
+mod png;
+mod raster;
+mod render;
+mod style;
+mod tikz;
 
 mod layout;
 
-pub use config::{CornerStyle, DiagramConfig};
+pub use config::{
+    CircleConfig, CirclePlacement, CornerStyle, DiagramConfig, LegendPosition, Styling,
+    ThresholdBand,
+};
+use std::ops::Range;
+
+pub use error::RenderError;
+pub use geometry::{Cycle, Geometry, Outline, Pie, Point, Segment, Slice};
+pub use html::html;
 use itertools::Itertools;
 pub use layout::{Layout, LayoutConst};
+pub use pdf::{EpsRenderer, PdfRenderer};
+pub use raster::PngRenderer;
+pub use render::{Renderer, Scene, SvgRenderer};
+pub use tikz::TikzRenderer;
 
 use self::{
-    circles::{Coalition, draw_circle},
-    corner::{BasicCorner, Corner, Diagonal, get_rounded_paths},
+    corner::{BasicCorner, Corner, Diagonal},
     offset::InnerOffset,
 };
-use super::direction::{DirectedEdge, Edge};
+pub use super::direction::DirectedEdge;
 use crate::{
     direction::Direction, matrix::Matrix, polyomino::Polyomino, svg::offset::inner_offset,
 };
 
-fn get_combined_paths(paths: Vec<Vec<DirectedEdge>>) -> Vec<Vec<DirectedEdge>> {
+fn get_combined_paths(paths: Vec<Vec<Vec<DirectedEdge>>>) -> Vec<Vec<DirectedEdge>> {
     let mut combined_paths: Vec<Vec<DirectedEdge>> = Vec::new();
-    for path in paths {
-        let mut out: Vec<DirectedEdge> = Vec::new();
-        let mut current: Option<DirectedEdge> = None;
-        for edge in path {
-            current = match current {
-                Some(current_edge) => current_edge.combine_directed(&edge).or_else(|| {
-                    out.push(current_edge);
-                    Some(edge)
-                }),
-                None => Some(edge),
-            };
-        }
+    for cycles in paths {
+        let mut combined: Vec<DirectedEdge> = Vec::new();
+        for cycle in cycles {
+            let mut out: Vec<DirectedEdge> = Vec::new();
+            let mut current: Option<DirectedEdge> = None;
+            for edge in cycle {
+                current = match current {
+                    Some(current_edge) => current_edge.combine_directed(&edge).or_else(|| {
+                        out.push(current_edge);
+                        Some(edge)
+                    }),
+                    None => Some(edge),
+                };
+            }
 
-        if let Some(current) = current {
-            if let Some(combined) = out[0].combine_directed(&current) {
-                out[0] = combined;
-            } else {
-                out.push(current);
+            if let Some(current) = current {
+                if let Some(combined) = out[0].combine_directed(&current) {
+                    out[0] = combined;
+                } else {
+                    out.push(current);
+                }
             }
+
+            combined.extend(out);
         }
 
-        combined_paths.push(out);
+        combined_paths.push(combined);
     }
     combined_paths
 }
 
-fn get_paths(polys: &[Vec<Edge>]) -> Vec<Vec<DirectedEdge>> {
-    let mut paths: Vec<Vec<DirectedEdge>> = Vec::new();
-
-    for edges in polys {
-        // 1. Create adjancy matrix
-        let l = edges.len();
-        let mut adj = Matrix::new(l, l, false);
-
-        for i in 0..l {
-            for j in 0..l {
-                if i == j {
-                    continue;
-                }
-                if edges[i].connected(&edges[j]) {
-                    adj[(j, i)] = true;
-                }
-            }
+/// The border of each polyomino consists of one or more cycles, stored one
+/// after another. Returns the range of each cycle in `path`.
+fn cycles(path: &[DirectedEdge]) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (i, edge) in path.iter().enumerate() {
+        if edge.to() == path[start].from() {
+            out.push(start..(i + 1));
+            start = i + 1;
         }
+    }
+    debug_assert!(start == path.len());
+    out
+}
 
-        let mut path: Vec<DirectedEdge> = Vec::new();
-
-        // current edge we're examining
-        let mut i: usize = 0;
-        let mut pre: Option<(usize, usize)> = None;
-        while let Some(j) = adj.row(i).iter().position(|x| *x) {
-            for k in 0..l {
-                adj[(k, i)] = false;
-                adj[(i, k)] = false;
-            }
-
-            let directed: Option<DirectedEdge>;
-            (directed, pre) = if let Some(op) = pre {
-                let edge = &edges[i];
-                let (e1, e2) = edge.endpoints();
-                if op == e1 {
-                    (DirectedEdge::from_endpoints(e1, e2), Some(e2))
-                } else if op == e2 {
-                    (DirectedEdge::from_endpoints(e2, e1), Some(e1))
-                } else {
-                    unreachable!();
-                }
-            } else {
-                let edge1 = &edges[i];
-                let edge2 = &edges[j];
-                let (a1, a2) = edge1.endpoints();
-                let (b1, b2) = edge2.endpoints();
-                if a1 == b1 || a1 == b2 {
-                    (DirectedEdge::from_endpoints(a2, a1), Some(a1))
-                } else if a2 == b1 || a2 == b2 {
-                    (DirectedEdge::from_endpoints(a1, a2), Some(a2))
-                } else {
-                    unreachable!();
-                }
-            };
+fn get_paths(
+    width: usize,
+    height: usize,
+    polys: &[Vec<DirectedEdge>],
+) -> Vec<Vec<Vec<DirectedEdge>>> {
+    let mut paths: Vec<Vec<Vec<DirectedEdge>>> = Vec::new();
 
-            path.push(directed.unwrap());
-            i = j;
+    for edges in polys {
+        // 1. Find the edges going out from each point
+        let mut outgoing: Matrix<Vec<usize>> = Matrix::new(width + 1, height + 1, Vec::new());
+        for (i, edge) in edges.iter().enumerate() {
+            outgoing[edge.from()].push(i);
         }
-        let directed = if let Some(op) = pre {
-            let edge = &edges[i];
-            let (e1, e2) = edge.endpoints();
-            if op == e1 {
-                DirectedEdge::from_endpoints(e1, e2)
-            } else if op == e2 {
-                DirectedEdge::from_endpoints(e2, e1)
-            } else {
-                unreachable!();
-            }
-        } else {
-            unreachable!();
-        };
 
-        path.push(directed.unwrap());
+        // 2. Follow edges until we get back to where we started. The first unused
+        // edge always starts a new cycle, which will be the outer border of a
+        // component or the border of a hole.
+        let mut used = vec![false; edges.len()];
+        let mut path: Vec<Vec<DirectedEdge>> = Vec::new();
+        while let Some(start) = used.iter().position(|x| !x) {
+            let mut cycle: Vec<DirectedEdge> = Vec::new();
+            let mut i = start;
+            loop {
+                used[i] = true;
+                let edge = edges[i];
+                cycle.push(edge);
+                if edge.to() == edges[start].from() {
+                    break;
+                }
 
-        // I don't think we need to handle holes or disjoint yet
-        // Let's just check we used every edge in this path
-        for i in 0..l {
-            for j in 0..l {
-                debug_assert!(!adj[(j, i)]);
+                // If the polyomino touches itself diagonally there are two edges to
+                // choose from. We turn towards the inside, i.e. clockwise, to keep
+                // the cells separate.
+                i = outgoing[edge.to()]
+                    .iter()
+                    .copied()
+                    .filter(|j| !used[*j])
+                    .min_by_key(|j| {
+                        match Direction::clockwise(edge.direction(), edges[*j].direction()) {
+                            Some(true) => 0,
+                            None => 1,
+                            Some(false) => 2,
+                        }
+                    })
+                    .unwrap();
             }
+            path.push(cycle);
         }
+
         paths.push(path);
     }
     paths
 }
 
-fn get_polys(width: usize, height: usize, polyominos: &[Polyomino]) -> Vec<Vec<Edge>> {
-    let mut polys: Vec<Vec<Edge>> = Vec::new();
+// The border of each polyomino, going clockwise around every cell so that the
+// inside is always to the right.
+pub(crate) fn get_polys(
+    width: usize,
+    height: usize,
+    polyominos: &[Polyomino],
+) -> Vec<Vec<DirectedEdge>> {
+    let mut polys: Vec<Vec<DirectedEdge>> = Vec::new();
 
     for poly in polyominos {
-        let mut edges: Vec<Edge> = Vec::new();
+        let mut edges: Vec<DirectedEdge> = Vec::new();
 
         for i in 0..width {
             for j in 0..height {
                 if poly[(i, j)] {
                     // Left
                     if i == 0 || !poly[(i - 1, j)] {
-                        edges.push(Edge::new_vertical(i, j, j + 1));
+                        edges.push(DirectedEdge::Vertical { x: i, y_from: j + 1, y_to: j });
                     }
                     // Up
                     if j == 0 || !poly[(i, j - 1)] {
-                        edges.push(Edge::new_horizontal(j, i, i + 1));
+                        edges.push(DirectedEdge::Horizontal { y: j, x_from: i, x_to: i + 1 });
                     }
                     // Right
                     if i == (width - 1) || !poly[(i + 1, j)] {
-                        edges.push(Edge::new_vertical(i + 1, j, j + 1));
+                        edges.push(DirectedEdge::Vertical { x: i + 1, y_from: j, y_to: j + 1 });
                     }
                     // Down
                     if j == (height - 1) || !poly[(i, j + 1)] {
-                        edges.push(Edge::new_horizontal(j + 1, i, i + 1));
+                        edges.push(DirectedEdge::Horizontal { y: j + 1, x_from: i + 1, x_to: i });
                     }
                 }
             }
@@ -176,7 +192,7 @@ fn get_points(
     offsets: &[Vec<i32>],
     line_width: f64,
     corner_offset: f64,
-) -> Vec<Vec<Corner>> {
+) -> Vec<Vec<Vec<Corner>>> {
     // We will convert to just points, with offsets applied
     let mut points: Vec<Vec<BasicCorner>> = Vec::new();
     let mut group_offsets: Vec<Vec<Option<i32>>> = Vec::new();
@@ -187,39 +203,43 @@ fn get_points(
     for (i, (path_edges, path_offsets)) in combined_paths.iter().zip(offsets).enumerate() {
         let mut out = Vec::new();
         let mut path_group_offsets = Vec::new();
-        let last_edge = path_edges.last().unwrap();
-        let last_offset = path_offsets.last().unwrap();
-
-        let path_edges = std::iter::once(last_edge).chain(path_edges);
-        let path_offsets = std::iter::once(last_offset).chain(path_offsets);
-        let parts: Vec<(&DirectedEdge, &i32)> = path_edges.zip(path_offsets).collect();
-        for (j, aa) in parts.windows(2).enumerate() {
-            let ((e1, o1), (e2, o2)) = (&aa[0], &aa[1]);
-            let (shared_x, shared_y) = e1.to();
-            debug_assert!(e1.to() == e2.from());
-
-            let (ox, oy) = match e1 {
-                DirectedEdge::Horizontal { .. } => (o2, o1),
-                DirectedEdge::Vertical { .. } => (o1, o2),
-            };
-
-            let corner = BasicCorner {
-                x: shared_x,
-                y: shared_y,
-                x_offset: **ox,
-                y_offset: **oy,
-                from: e1.direction(),
-                to: e2.direction(),
-            };
-
-            // We store them in the order of the path
-            out.push(corner);
-
-            // We also store the corsers grouped by their x and y position
-            positioned_corners[(shared_x, shared_y)].push((i, j));
-
-            // We'll fill these in later
-            path_group_offsets.push(None);
+        for cycle in cycles(path_edges) {
+            let cycle_edges = &path_edges[cycle.clone()];
+            let cycle_offsets = &path_offsets[cycle];
+            let last_edge = cycle_edges.last().unwrap();
+            let last_offset = cycle_offsets.last().unwrap();
+
+            let cycle_edges = std::iter::once(last_edge).chain(cycle_edges);
+            let cycle_offsets = std::iter::once(last_offset).chain(cycle_offsets);
+            let parts: Vec<(&DirectedEdge, &i32)> = cycle_edges.zip(cycle_offsets).collect();
+            for aa in parts.windows(2) {
+                let ((e1, o1), (e2, o2)) = (&aa[0], &aa[1]);
+                let (shared_x, shared_y) = e1.to();
+                debug_assert!(e1.to() == e2.from());
+
+                let (ox, oy) = match e1 {
+                    DirectedEdge::Horizontal { .. } => (o2, o1),
+                    DirectedEdge::Vertical { .. } => (o1, o2),
+                };
+
+                let corner = BasicCorner {
+                    x: shared_x,
+                    y: shared_y,
+                    x_offset: **ox,
+                    y_offset: **oy,
+                    from: e1.direction(),
+                    to: e2.direction(),
+                };
+
+                // We also store the corsers grouped by their x and y position
+                positioned_corners[(shared_x, shared_y)].push((i, out.len()));
+
+                // We store them in the order of the path
+                out.push(corner);
+
+                // We'll fill these in later
+                path_group_offsets.push(None);
+            }
         }
         points.push(out);
         group_offsets.push(path_group_offsets);
@@ -264,7 +284,8 @@ fn get_points(
     }
 
     let mut other_points = Vec::new();
-    for (path, path_offsets) in points.iter().zip(&group_offsets) {
+    for ((path, path_offsets), path_edges) in points.iter().zip(&group_offsets).zip(combined_paths)
+    {
         let mut other_out = Vec::new();
         for (corner, offset) in path.iter().zip(path_offsets) {
             let offset = (offset.unwrap_or(0) as f64).mul_add(line_width, corner_offset);
@@ -292,7 +313,83 @@ fn get_points(
             let corner = Corner { from, to, clockwise, radius: offset };
             other_out.push(corner);
         }
-        other_points.push(other_out);
+
+        // Each cycle has as many corners as edges
+        let mut other_out = other_out.into_iter();
+        let path_cycles =
+            cycles(path_edges).into_iter().map(|x| other_out.by_ref().take(x.len()).collect());
+        other_points.push(path_cycles.collect());
     }
     other_points
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants, diagram::Diagram};
+
+    fn cycle_counts(diagram: &Diagram) -> Vec<usize> {
+        let (width, height) = (diagram.width(), diagram.height());
+        let polys = get_polys(width, height, &diagram.polyominos);
+        let combined_paths = get_combined_paths(get_paths(width, height, &polys));
+        combined_paths.iter().map(|path| cycles(path).len()).collect()
+    }
+
+    #[test]
+    fn holes_and_components() {
+        let diagram =
+            Diagram::from_binary_grids(&["1111\n1001\n1111", "0100\n0110\n1000"]).unwrap();
+        assert_eq!(cycle_counts(&diagram), vec![2, 2]);
+    }
+
+    // Edges of each polyomino, ignoring where each cycle starts
+    fn sorted_edges(layout: &Layout) -> Vec<Vec<[(usize, usize); 2]>> {
+        let edges = layout
+            .combined_paths
+            .iter()
+            .map(|path| path.iter().map(|edge| [edge.from(), edge.to()]).sorted().collect());
+        edges.collect()
+    }
+
+    #[test]
+    fn transforms() {
+        let diagrams: [Diagram; 4] = [
+            constants::d3::THREE.into(),
+            constants::d5::FIVE.into(),
+            constants::d6::SIX.into(),
+            constants::d7::SEVEN.into(),
+        ];
+        for diagram in diagrams {
+            let layout = diagram.clone().layout_greedy();
+            let transformed = [
+                layout.rotate90(),
+                layout.flip_horizontal(),
+                layout.flip_vertical(),
+                layout.transpose(),
+            ];
+            let expected = [
+                diagram.rotate90(),
+                diagram.flip_horizontal(),
+                diagram.flip_vertical(),
+                diagram.transpose(),
+            ];
+            for (transformed, expected) in transformed.iter().zip(expected) {
+                let expected = expected.layout_greedy();
+                assert_eq!(transformed.diagram, expected.diagram);
+                assert_eq!(sorted_edges(transformed), sorted_edges(&expected));
+            }
+
+            assert_eq!(layout.rotate90().rotate90().rotate90().rotate90(), layout);
+            assert_eq!(layout.flip_horizontal().flip_horizontal(), layout);
+            assert_eq!(layout.flip_vertical().flip_vertical(), layout);
+            assert_eq!(layout.transpose().transpose(), layout);
+        }
+    }
+
+    #[test]
+    fn touching_diagonally() {
+        let diagram =
+            Diagram::from_binary_grids(&["1100\n1010\n0110", "0010\n0100\n0000"]).unwrap();
+        assert_eq!(cycle_counts(&diagram), vec![2, 2]);
+    }
+}
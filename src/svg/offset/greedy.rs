@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use crate::{
     direction::{DirectedEdge, Direction, Edge},
     matrix::Matrix,
-    svg::offset::EdgeInfo,
+    svg::{cycles, offset::EdgeInfo},
 };
 
 pub(super) fn get_offsets(
@@ -20,15 +20,18 @@ pub(super) fn get_offsets(
 
     for path in combined_paths {
         let mut path_directions: Vec<Option<Direction>> = Vec::new();
-        let mut edges_extra: Vec<Direction> = Vec::with_capacity(path.len() + 2);
-        edges_extra.push(path.last().unwrap().direction());
-        edges_extra.extend(path.iter().map(DirectedEdge::direction));
-        edges_extra.push(path.last().unwrap().direction());
-
-        for window in edges_extra.windows(3) {
-            if let [e0, _, e2] = window {
-                let res = if *e0 == e2.opposite() { Some(*e2) } else { None };
-                path_directions.push(res);
+        for cycle in cycles(path) {
+            let cycle = &path[cycle];
+            let mut edges_extra: Vec<Direction> = Vec::with_capacity(cycle.len() + 2);
+            edges_extra.push(cycle.last().unwrap().direction());
+            edges_extra.extend(cycle.iter().map(DirectedEdge::direction));
+            edges_extra.push(cycle.last().unwrap().direction());
+
+            for window in edges_extra.windows(3) {
+                if let [e0, _, e2] = window {
+                    let res = if *e0 == e2.opposite() { Some(*e2) } else { None };
+                    path_directions.push(res);
+                }
             }
         }
 
@@ -83,9 +83,11 @@ pub(super) fn inner_offset(
 impl Diagram {
     /// Decide offsets greedily, placing larger edges before smaller edges.
     /// Positions are calculated seperately for each column and each row.
+    ///
+    /// Polyominos may contain holes or consist of multiple components.
     pub fn layout_greedy(self) -> Layout {
         let polys = get_polys(self.width(), self.height(), &self.polyominos);
-        let paths = get_paths(&polys);
+        let paths = get_paths(self.width(), self.height(), &polys);
         let combined_paths = get_combined_paths(paths);
         let offsets = greedy::get_offsets(self.width(), self.height(), &combined_paths);
 
@@ -99,10 +101,12 @@ impl Diagram {
     }
 
     /// Decide offsets by optimization, minimizing edge overlaps and gaps.
+    ///
+    /// Polyominos may contain holes or consist of multiple components.
     #[cfg(feature = "optimize")]
     pub fn layout_optimize(self) -> Layout {
         let polys = get_polys(self.width(), self.height(), &self.polyominos);
-        let paths = get_paths(&polys);
+        let paths = get_paths(self.width(), self.height(), &polys);
         let combined_paths = get_combined_paths(paths);
         let offsets = optimizing::get_offsets(self.width(), self.height(), &combined_paths);
 
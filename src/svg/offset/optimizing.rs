@@ -8,7 +8,7 @@ use z3::{
 use crate::{
     direction::{DirectedEdge, Direction, Edge},
     matrix::Matrix,
-    svg::Diagonal,
+    svg::{Diagonal, cycles},
 };
 
 #[derive(Debug, Clone)]
@@ -168,35 +168,37 @@ pub(super) fn get_offsets(
 
     // Find which crossings corners are on
     for (path, variables) in combined_paths.iter().zip(&path_variables) {
-        let mut edges: Vec<_> = path.iter().zip(variables).collect();
-        edges.push(edges[0]);
-
-        for window in edges.windows(2) {
-            if let [(edge_from, variable_from), (edge_to, variable_to)] = *window {
-                debug_assert!(edge_from.to() == edge_to.from());
-                let from_vertical: bool = match edge_from.direction() {
-                    Direction::Left | Direction::Right => false,
-                    Direction::Up | Direction::Down => true,
-                };
-
-                let diagonal = Diagonal::from_directions(
-                    edge_from.direction().opposite(),
-                    edge_to.direction(),
-                )
-                .unwrap();
-
-                let (int_vertical, int_horizontal) = if from_vertical {
-                    (variable_from.clone(), variable_to.clone())
+        for cycle in cycles(path) {
+            let mut edges: Vec<_> = path[cycle.clone()].iter().zip(&variables[cycle]).collect();
+            edges.push(edges[0]);
+
+            for window in edges.windows(2) {
+                if let [(edge_from, variable_from), (edge_to, variable_to)] = *window {
+                    debug_assert!(edge_from.to() == edge_to.from());
+                    let from_vertical: bool = match edge_from.direction() {
+                        Direction::Left | Direction::Right => false,
+                        Direction::Up | Direction::Down => true,
+                    };
+
+                    let diagonal = Diagonal::from_directions(
+                        edge_from.direction().opposite(),
+                        edge_to.direction(),
+                    )
+                    .unwrap();
+
+                    let (int_vertical, int_horizontal) = if from_vertical {
+                        (variable_from.clone(), variable_to.clone())
+                    } else {
+                        (variable_to.clone(), variable_from.clone())
+                    };
+
+                    let corner = Corner { int_vertical, diagonal, int_horizontal };
+
+                    let meets = edge_from.to();
+                    crossings[meets].corners.push(corner);
                 } else {
-                    (variable_to.clone(), variable_from.clone())
-                };
-
-                let corner = Corner { int_vertical, diagonal, int_horizontal };
-
-                let meets = edge_from.to();
-                crossings[meets].corners.push(corner);
-            } else {
-                unreachable!();
+                    unreachable!();
+                }
             }
         }
     }
@@ -0,0 +1,387 @@
+use std::{
+    f64::consts::{FRAC_PI_2, TAU},
+    fmt::Write,
+};
+
+use super::{
+    Outline, Point, RenderError, Renderer, Scene,
+    color::{Rgb, parse_color_or_err},
+    geometry::{Resolved, arc_curves},
+    legend::{FONT_SIZE, Placement, ROW_HEIGHT, SPACING, SWATCH, legend_entries, place_legend},
+    render::num,
+};
+
+const POINTS_PER_CM: f64 = 72.0 / 2.54;
+
+/// Draws diagrams as PDF documents with a single page, see [`Renderer`].
+///
+/// The legend uses Helvetica, and colors are limited as described in
+/// [`RenderError::InvalidColor`].
+///
+/// ```
+/// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+/// use venn_diagrams::svg::{DiagramConfig, Layout, PdfRenderer, Renderer};
+///
+/// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+/// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+/// let config = DiagramConfig::default();
+/// let scene = layout.scene(&[0.3, 0.3, 0.4], colors, &config).unwrap();
+/// let pdf = PdfRenderer::default().render(&scene).unwrap();
+/// assert!(pdf.starts_with(b"%PDF-1.4"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PdfRenderer {
+    /// Width of a cell in centimeters.
+    pub unit: f64,
+}
+
+impl Default for PdfRenderer {
+    fn default() -> Self {
+        Self { unit: 1.0 }
+    }
+}
+
+/// Draws diagrams as EPS files, see [`Renderer`] and [`PdfRenderer`].
+///
+/// PostScript has no transparency, so colors with an opacity are mixed with
+/// black instead, which is exact where the polyominos don't overlap.
+#[derive(Debug, Clone, Copy)]
+pub struct EpsRenderer {
+    /// Width of a cell in centimeters.
+    pub unit: f64,
+}
+
+impl Default for EpsRenderer {
+    fn default() -> Self {
+        Self { unit: 1.0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Pdf,
+    Eps,
+}
+
+// Writes the operators of PDF or PostScript, which draw paths the same way.
+// Opacities are collected as graphics states for PDF.
+struct Painter {
+    format: Format,
+    out: String,
+    opacities: Vec<f64>,
+}
+
+// A string of PDF or PostScript, with characters outside of Latin-1 replaced
+fn string(text: &str) -> String {
+    let mut out = String::from("(");
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            _ => match u8::try_from(u32::from(c)) {
+                Ok(byte) if byte >= 0xa0 => out += &format!("\\{byte:03o}"),
+                _ => out.push('?'),
+            },
+        }
+    }
+    out.push(')');
+    out
+}
+
+impl Painter {
+    // Writing to a `String` never fails
+    fn op(&mut self, args: &[f64], pdf: &str, eps: &str) {
+        for &x in args {
+            write!(self.out, "{} ", num(x)).unwrap();
+        }
+        self.out += if self.format == Format::Pdf { pdf } else { eps };
+        self.out.push('\n');
+    }
+
+    fn move_to(&mut self, (x, y): Point) {
+        self.op(&[x, y], "m", "moveto");
+    }
+
+    fn line_to(&mut self, (x, y): Point) {
+        self.op(&[x, y], "l", "lineto");
+    }
+
+    fn arc(&mut self, center: Point, radius: f64, start: f64, end: f64) {
+        for [(x1, y1), (x2, y2), (x3, y3)] in arc_curves(center, radius, start, end) {
+            self.op(&[x1, y1, x2, y2, x3, y3], "c", "curveto");
+        }
+    }
+
+    fn close(&mut self) {
+        self.op(&[], "h", "closepath");
+    }
+
+    fn outline(&mut self, outline: &Outline) {
+        for cycle in &outline.cycles {
+            self.move_to(cycle.start);
+            for segment in cycle.resolved() {
+                match segment {
+                    Resolved::Arc { center, radius, start, end, .. } => {
+                        self.arc(center, radius, start, end);
+                    }
+                    Resolved::Line { to } => self.line_to(to),
+                }
+            }
+            self.close();
+        }
+    }
+
+    // Set the color of both fills and strokes
+    fn color(&mut self, rgb: Rgb, opacity: f64) {
+        let [r, g, b] = rgb.map(|x| f64::from(x) / 255.0);
+        match self.format {
+            Format::Pdf => {
+                let i = self.opacities.iter().position(|&x| x == opacity).unwrap_or_else(|| {
+                    self.opacities.push(opacity);
+                    self.opacities.len() - 1
+                });
+                let [r, g, b] = [r, g, b].map(num);
+                writeln!(self.out, "{r} {g} {b} rg {r} {g} {b} RG /GS{i} gs").unwrap();
+            }
+            Format::Eps => self.op(&[r * opacity, g * opacity, b * opacity], "", "setrgbcolor"),
+        }
+    }
+
+    fn fill(&mut self, even_odd: bool) {
+        if even_odd {
+            self.op(&[], "f*", "eofill");
+        } else {
+            self.op(&[], "f", "fill");
+        }
+    }
+
+    fn stroke(&mut self, width: f64) {
+        self.op(&[width], "w", "setlinewidth");
+        self.op(&[], "S", "stroke");
+    }
+
+    // Text which is vertically centered on `y`
+    fn text(&mut self, (x, y): Point, text: &str) {
+        // The middle of capital letters is about a third of the size above
+        // the baseline
+        let y = y + FONT_SIZE / 3.0;
+        let text = string(text);
+        match self.format {
+            Format::Pdf => {
+                let [x, y, size] = [x, y, FONT_SIZE].map(num);
+                writeln!(self.out, "BT /F1 {size} Tf 1 0 0 -1 {x} {y} Tm {text} Tj ET").unwrap();
+            }
+            Format::Eps => {
+                self.move_to((x, y));
+                writeln!(self.out, "{text} show").unwrap();
+            }
+        }
+    }
+}
+
+// Draw a scene in the coordinates of the diagram, and return the painter and
+// the size of the page in cells
+fn paint(scene: &Scene<'_>, format: Format) -> Result<(Painter, (f64, f64)), RenderError> {
+    let Scene { geometry, colors, config, .. } = scene;
+    let colors =
+        colors.iter().map(|color| parse_color_or_err(color)).collect::<Result<Vec<_>, _>>()?;
+    let mut painter = Painter { format, out: String::new(), opacities: Vec::new() };
+
+    let black = [0, 0, 0];
+    painter.color(black, 1.0);
+    for outline in &geometry.outlines {
+        painter.outline(outline);
+        painter.fill(true);
+    }
+    for (outline, &color) in geometry.outlines.iter().zip(&colors) {
+        painter.color(color, config.fill_opacity);
+        painter.outline(outline);
+        painter.fill(true);
+    }
+    for (outline, &color) in geometry.outlines.iter().zip(&colors) {
+        painter.color(color, 1.0);
+        painter.outline(outline);
+        painter.stroke(config.line_width);
+    }
+
+    for pie in &geometry.pies {
+        let circle = pie.circle(config);
+        let opacity = circle.map_or(1.0, |circle| circle.opacity);
+        for slice in &pie.slices {
+            // Slices start straight up, but angles start to the right
+            let (start, end) = (slice.start - FRAC_PI_2, slice.end - FRAC_PI_2);
+            let (cx, cy) = pie.center;
+            painter.color(colors[slice.set], opacity);
+            painter.move_to(pie.center);
+            painter.line_to((cx + pie.radius * start.cos(), cy + pie.radius * start.sin()));
+            painter.arc(pie.center, pie.radius, start, end);
+            painter.close();
+            painter.fill(false);
+        }
+        if let Some(circle) = circle {
+            painter.color(parse_color_or_err(&circle.color)?, opacity);
+            painter.move_to((pie.center.0 + pie.radius, pie.center.1));
+            painter.arc(pie.center, pie.radius, 0.0, TAU);
+            painter.close();
+            painter.stroke(0.025);
+        }
+    }
+
+    let (_, _, width, height) = geometry.bounds;
+    let entries = legend_entries(scene);
+    let Some(Placement { positions, width: extra_width, height: extra_height }) =
+        place_legend(&entries, config.legend, geometry.bounds)
+    else {
+        return Ok((painter, (width, height)));
+    };
+    for (i, (_, text)) in entries.iter().enumerate() {
+        let (x, y) = positions[i];
+        let y = y + (ROW_HEIGHT - SWATCH) / 2.0;
+        painter.color(colors[i], 1.0);
+        painter.move_to((x, y));
+        painter.line_to((x + SWATCH, y));
+        painter.line_to((x + SWATCH, y + SWATCH));
+        painter.line_to((x, y + SWATCH));
+        painter.close();
+        painter.fill(false);
+        painter.color(black, 1.0);
+        painter.text((x + SWATCH + SPACING, positions[i].1 + ROW_HEIGHT / 2.0), text);
+    }
+    Ok((painter, (width + extra_width, height + extra_height)))
+}
+
+// The matrix from the coordinates of the diagram to points, where `y` grows
+// upwards, and the size of the page in points
+fn transform(scene: &Scene<'_>, unit: f64, (width, height): (f64, f64)) -> (String, f64, f64) {
+    let (min_x, min_y, ..) = scene.geometry.bounds;
+    let scale = unit * POINTS_PER_CM;
+    let matrix = [scale, 0.0, 0.0, -scale, -scale * min_x, scale * (min_y + height)];
+    (matrix.map(num).join(" "), scale * width, scale * height)
+}
+
+impl Renderer for PdfRenderer {
+    type Output = Result<Vec<u8>, RenderError>;
+
+    fn render(&self, scene: &Scene<'_>) -> Self::Output {
+        let (painter, size) = paint(scene, Format::Pdf)?;
+        let (matrix, width, height) = transform(scene, self.unit, size);
+        let content = format!("{matrix} cm\n{}", painter.out);
+
+        let states: String = painter
+            .opacities
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| format!(" /GS{i} << /Type /ExtGState /ca {0} /CA {0} >>", num(x)))
+            .collect();
+        let objects = [
+            String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+            String::from("<< /Type /Pages /Kids [3 0 R] /Count 1 >>"),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents 4 0 R \
+                 /Resources << /ExtGState <<{states} >> /Font << /F1 5 0 R >> >> >>",
+                num(width),
+                num(height)
+            ),
+            format!("<< /Length {} >>\nstream\n{content}endstream", content.len()),
+            String::from(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica \
+                 /Encoding /WinAnsiEncoding >>",
+            ),
+        ];
+
+        // The header has a comment with bytes above 127, which marks the file
+        // as binary
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend(format!("{} 0 obj\n{object}\nendobj\n", i + 1).bytes());
+        }
+        let xref = out.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            table += &format!("{offset:010} 00000 n \n");
+        }
+        out.extend(table.bytes());
+        let trailer = format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend(trailer.bytes());
+        Ok(out)
+    }
+}
+
+// Helvetica with Latin-1 characters, like the WinAnsi encoding of PDF
+const EPS_FONT: &str = "/Helvetica findfont dup length dict begin
+{1 index /FID ne {def} {pop pop} ifelse} forall
+/Encoding ISOLatin1Encoding def
+currentdict end /Helvetica-Latin1 exch definefont pop
+";
+
+impl Renderer for EpsRenderer {
+    type Output = Result<String, RenderError>;
+
+    fn render(&self, scene: &Scene<'_>) -> Self::Output {
+        let (painter, size) = paint(scene, Format::Eps)?;
+        let (matrix, width, height) = transform(scene, self.unit, size);
+
+        let mut out = String::new();
+        let w = &mut out;
+        writeln!(w, "%!PS-Adobe-3.0 EPSF-3.0").unwrap();
+        writeln!(w, "%%BoundingBox: 0 0 {} {}", width.ceil(), height.ceil()).unwrap();
+        writeln!(w, "%%HiResBoundingBox: 0 0 {} {}", num(width), num(height)).unwrap();
+        writeln!(w, "%%Creator: venn-diagrams").unwrap();
+        writeln!(w, "%%EndComments").unwrap();
+        w.push_str(EPS_FONT);
+        // The font is flipped, since `y` grows downwards in the diagram
+        let size = num(FONT_SIZE);
+        writeln!(w, "/Helvetica-Latin1 findfont [{size} 0 0 -{size} 0 0] makefont setfont")
+            .unwrap();
+        writeln!(w, "[{matrix}] concat").unwrap();
+        w.push_str(&painter.out);
+        writeln!(w, "showpage\n%%EOF").unwrap();
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::d3::LAYOUT_OPTIMIZED_THREE, svg::DiagramConfig, svg::Layout};
+
+    #[test]
+    fn strings() {
+        assert_eq!(string("S (50%)"), "(S \\(50%\\))");
+        assert_eq!(string("Höger"), "(H\\366ger)");
+        assert_eq!(string("中"), "(?)");
+    }
+
+    #[test]
+    fn cross_references() {
+        let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+        let config = DiagramConfig::default();
+        let scene = layout.scene(&[0.3, 0.3, 0.4], &["red", "green", "blue"], &config).unwrap();
+        let pdf = PdfRenderer::default().render(&scene).unwrap();
+
+        // Every offset in the cross-reference table points to its object
+        let find = |pattern: &[u8]| pdf.windows(pattern.len()).position(|w| w == pattern);
+        let xref = find(b"\nxref\n").unwrap() + 1;
+        let tail = String::from_utf8_lossy(&pdf[xref..]);
+        let lines: Vec<&str> = tail.lines().collect();
+        assert_eq!(lines.last(), Some(&"%%EOF"));
+        assert_eq!(lines[lines.len() - 2], xref.to_string());
+        for (i, line) in lines[3..8].iter().enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+
+        let colors = &["red", "green", "nope"];
+        let scene = layout.scene(&[0.3, 0.3, 0.4], colors, &config).unwrap();
+        let error = RenderError::InvalidColor { color: String::from("nope") };
+        assert_eq!(EpsRenderer::default().render(&scene), Err(error));
+    }
+}
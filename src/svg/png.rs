@@ -0,0 +1,180 @@
+// A minimal PNG encoder for 8-bit RGBA images. Rows are filtered so that
+// areas of one color become runs of zeros, which are compressed with the
+// fixed Huffman codes of deflate.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Base and number of extra bits of the deflate length codes 257 to 285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] =
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+// Writes bits starting from the least significant bit of each byte
+#[derive(Default)]
+struct Bits {
+    out: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits {
+    fn write(&mut self, value: u32, count: u32) {
+        for i in 0..count {
+            self.buffer |= ((value >> i) & 1) << self.count;
+            self.count += 1;
+            if self.count == 8 {
+                self.out.push(self.buffer as u8);
+                (self.buffer, self.count) = (0, 0);
+            }
+        }
+    }
+
+    // Huffman codes start with their most significant bit
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn symbol(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.buffer as u8);
+        }
+        self.out
+    }
+}
+
+// Compress with a single block of fixed Huffman codes, where repeated bytes
+// are encoded as copies of the previous byte
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut bits = Bits::default();
+    // The final block, with fixed codes
+    bits.write(1, 1);
+    bits.write(1, 2);
+    let mut i = 0;
+    while i < data.len() {
+        let run = match i {
+            0 => 0,
+            _ => data[i..].iter().take(258).take_while(|&&x| x == data[i - 1]).count(),
+        };
+        if run < 3 {
+            bits.symbol(u32::from(data[i]));
+            i += 1;
+            continue;
+        }
+        let code = LENGTH_BASE.iter().rposition(|&base| usize::from(base) <= run).unwrap();
+        bits.symbol(257 + code as u32);
+        bits.write((run - usize::from(LENGTH_BASE[code])) as u32, LENGTH_EXTRA[code]);
+        // Distance 1 has code 0 without extra bits
+        bits.write_code(0, 5);
+        i += run;
+    }
+    bits.symbol(256);
+    bits.finish()
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+// Encode an image with 4 bytes per pixel, row by row
+pub(super) fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let stride = 4 * width as usize;
+    debug_assert_eq!(rgba.len(), stride * height as usize);
+
+    // Each row is filtered by subtracting the pixel to the left or the one
+    // above, whichever gives the most zeros
+    let mut data = Vec::with_capacity((stride + 1) * height as usize);
+    let mut previous: Option<&[u8]> = None;
+    for row in rgba.chunks(stride) {
+        let sub = (0..stride).map(|i| if i < 4 { row[i] } else { row[i].wrapping_sub(row[i - 4]) });
+        let sub: Vec<u8> = sub.collect();
+        let up: Option<Vec<u8>> =
+            previous.map(|above| row.iter().zip(above).map(|(x, y)| x.wrapping_sub(*y)).collect());
+        let zeros = |filtered: &[u8]| filtered.iter().filter(|&&x| x == 0).count();
+        match up {
+            Some(up) if zeros(&up) > zeros(&sub) => {
+                data.push(2);
+                data.extend(up);
+            }
+            _ => {
+                data.push(1);
+                data.extend(sub);
+            }
+        }
+        previous = Some(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    zlib.extend(deflate(&data));
+    zlib.extend(adler32(&data).to_be_bytes());
+
+    let mut header = Vec::new();
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGBA, and the default compression, filters and no
+    // interlacing
+    header.extend([8, 6, 0, 0, 0]);
+
+    let mut out = SIGNATURE.to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    chunk(&mut out, b"IDAT", &zlib);
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn runs() {
+        // A literal, a copy of 257 bytes and the end of the block
+        let deflated = deflate(&[0; 258]);
+        assert_eq!(deflated.len(), 5);
+        let png = encode_png(1, 1, &[255, 0, 0, 255]);
+        assert!(png.starts_with(&SIGNATURE));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    }
+}
@@ -0,0 +1,419 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use super::{
+    DiagramConfig, Layout, LegendPosition, Outline, Point, RenderError, Renderer, Scene,
+    color::{Rgb, parse_color_or_err},
+    geometry::Resolved,
+    png::encode_png,
+};
+
+// Rows of samples per pixel, while the coverage of each row is exact
+const SAMPLES: usize = 16;
+
+// How far flattened arcs may be from the real arcs, in pixels
+const TOLERANCE: f64 = 0.1;
+
+/// Draws diagrams as PNG images with anti-aliasing, see [`Renderer`] and
+/// [`Layout::to_png`].
+///
+/// Like [`PdfRenderer`](super::PdfRenderer), this only supports some colors,
+/// see [`RenderError::InvalidColor`]. There is no font to draw the text of
+/// the legend with, so [`DiagramConfig::legend`] must be
+/// [`LegendPosition::None`], otherwise [`RenderError::InvalidConfig`] is
+/// returned.
+///
+/// Images which are empty or too large give
+/// [`RenderError::InvalidImageSize`], so that untrusted sizes can't use up
+/// the memory.
+#[derive(Debug, Clone, Copy)]
+pub struct PngRenderer {
+    /// Width of the image in pixels.
+    pub width: u32,
+}
+
+impl PngRenderer {
+    /// Largest width and height of an image in pixels.
+    pub const MAX_SIZE: u32 = 16384;
+
+    /// Largest number of pixels of an image.
+    pub const MAX_PIXELS: u64 = 1 << 24;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+// An image with premultiplied colors from 0 to 1, and the transform from the
+// coordinates of the diagram to pixels
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f64; 4]>,
+    origin: Point,
+    scale: f64,
+}
+
+// Add `weight` times the part of each pixel between `from` and `to` on a row
+fn add_span(coverage: &mut [f64], from: f64, to: f64, weight: f64) {
+    let width = coverage.len() as f64;
+    let (from, to) = (from.clamp(0.0, width), to.clamp(0.0, width));
+    if from >= to {
+        return;
+    }
+    let (first, last) = (from.floor() as usize, to.floor() as usize);
+    if first == last {
+        coverage[first] += (to - from) * weight;
+        return;
+    }
+    coverage[first] += (first as f64 + 1.0 - from) * weight;
+    for x in &mut coverage[first + 1..last] {
+        *x += weight;
+    }
+    if last < coverage.len() {
+        coverage[last] += (to - last as f64) * weight;
+    }
+}
+
+fn signed_area(polygon: &[Point]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+// Polygons covering the stroke of a closed polyline, with miter joins like in
+// SVG. They all have the same orientation, so that their union is filled by
+// the nonzero rule.
+fn stroke(points: &[Point], width: f64) -> Vec<Vec<Point>> {
+    let mut points = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let h = width / 2.0;
+    let normal = |a: Point, b: Point| {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let length = dx.hypot(dy);
+        (-dy / length, dx / length)
+    };
+    let offset =
+        |p: Point, (nx, ny): Point, distance: f64| (p.0 + distance * nx, p.1 + distance * ny);
+
+    let mut out = Vec::new();
+    for i in 0..n {
+        let (a, b, c) = (points[i], points[(i + 1) % n], points[(i + 2) % n]);
+        let n1 = normal(a, b);
+        out.push(vec![offset(a, n1, h), offset(b, n1, h), offset(b, n1, -h), offset(a, n1, -h)]);
+
+        // The join at `b` fills the gap on the outside of the turn. The miter
+        // is cut off where it's more than 4 times as long as the width, which
+        // is the default miter limit of SVG.
+        let n2 = normal(b, c);
+        let turn = (c.0 - b.0) * n1.0 + (c.1 - b.1) * n1.1;
+        let side = if turn > 0.0 { -h } else { h };
+        let sum = (n1.0 + n2.0, n1.1 + n2.1);
+        let cos = sum.0.hypot(sum.1) / 2.0;
+        let mut join = vec![b, offset(b, n1, side)];
+        if cos > 0.25 {
+            join.push(offset(b, sum, side / (2.0 * cos * cos)));
+        }
+        join.push(offset(b, n2, side));
+        out.push(join);
+    }
+    for polygon in &mut out {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    out
+}
+
+impl Canvas {
+    fn pixel(&self, (x, y): Point) -> Point {
+        ((x - self.origin.0) * self.scale, (y - self.origin.1) * self.scale)
+    }
+
+    // Add the points of an arc after its start, see `arc_angles`
+    fn arc(&self, points: &mut Vec<Point>, center: Point, radius: f64, start: f64, end: f64) {
+        let r = radius * self.scale;
+        let step = if r > TOLERANCE { 2.0 * (1.0 - TOLERANCE / r).acos() } else { FRAC_PI_2 };
+        let count = ((end - start).abs() / step).ceil().max(1.0) as usize;
+        for i in 1..=count {
+            let angle = start + (end - start) * i as f64 / count as f64;
+            points.push((center.0 + radius * angle.cos(), center.1 + radius * angle.sin()));
+        }
+    }
+
+    fn circle(&self, center: Point, radius: f64) -> Vec<Point> {
+        let mut points = Vec::new();
+        self.arc(&mut points, center, radius, 0.0, TAU);
+        points
+    }
+
+    // One closed polyline for each cycle of an outline
+    fn outline(&self, outline: &Outline) -> Vec<Vec<Point>> {
+        let mut out = Vec::new();
+        for cycle in &outline.cycles {
+            let mut points = vec![cycle.start];
+            for segment in cycle.resolved() {
+                match segment {
+                    Resolved::Arc { to, center, radius, start, end } => {
+                        self.arc(&mut points, center, radius, start, end);
+                        *points.last_mut().unwrap() = to;
+                    }
+                    Resolved::Line { to } => points.push(to),
+                }
+            }
+            out.push(points);
+        }
+        out
+    }
+
+    // Fill polygons, given in the coordinates of the diagram
+    fn fill(&mut self, polygons: &[Vec<Point>], rule: FillRule, color: Rgb, opacity: f64) {
+        // The edges in pixels, grouped by the rows of pixels they cross
+        let mut rows: Vec<Vec<(Point, Point)>> = vec![Vec::new(); self.height];
+        for polygon in polygons {
+            for (i, &a) in polygon.iter().enumerate() {
+                let (a, b) = (self.pixel(a), self.pixel(polygon[(i + 1) % polygon.len()]));
+                if a.1 == b.1 {
+                    continue;
+                }
+                let top = a.1.min(b.1).floor().max(0.0) as usize;
+                let bottom = (a.1.max(b.1).ceil().max(0.0) as usize).min(self.height);
+                for row in rows.iter_mut().take(bottom).skip(top) {
+                    row.push((a, b));
+                }
+            }
+        }
+
+        let color = color.map(|x| f64::from(x) / 255.0);
+        let weight = 1.0 / SAMPLES as f64;
+        let mut coverage = vec![0.0; self.width];
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+        for (y, edges) in rows.iter().enumerate() {
+            if edges.is_empty() {
+                continue;
+            }
+            coverage.fill(0.0);
+            for sample in 0..SAMPLES {
+                let sample_y = y as f64 + (sample as f64 + 0.5) * weight;
+                crossings.clear();
+                for &(a, b) in edges {
+                    if (a.1 <= sample_y) != (b.1 <= sample_y) {
+                        let x = a.0 + (sample_y - a.1) / (b.1 - a.1) * (b.0 - a.0);
+                        crossings.push((x, if b.1 > a.1 { 1 } else { -1 }));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match rule {
+                        FillRule::NonZero => winding != 0,
+                        FillRule::EvenOdd => winding % 2 != 0,
+                    };
+                    if inside {
+                        add_span(&mut coverage, pair[0].0, pair[1].0, weight);
+                    }
+                }
+            }
+
+            let row = &mut self.pixels[y * self.width..(y + 1) * self.width];
+            for (pixel, &covered) in row.iter_mut().zip(&coverage) {
+                let alpha = covered.min(1.0) * opacity;
+                if alpha > 0.0 {
+                    for i in 0..3 {
+                        pixel[i] = color[i].mul_add(alpha, pixel[i] * (1.0 - alpha));
+                    }
+                    pixel[3] = alpha + pixel[3] * (1.0 - alpha);
+                }
+            }
+        }
+    }
+
+    // The image with 4 bytes per pixel, which aren't premultiplied
+    fn rgba(&self) -> Vec<u8> {
+        let byte = |x: f64| (255.0 * x).round().clamp(0.0, 255.0) as u8;
+        let mut out = Vec::with_capacity(4 * self.pixels.len());
+        for &[r, g, b, a] in &self.pixels {
+            if a > 0.0 {
+                out.extend([byte(r / a), byte(g / a), byte(b / a), byte(a)]);
+            } else {
+                out.extend([0; 4]);
+            }
+        }
+        out
+    }
+}
+
+impl Renderer for PngRenderer {
+    type Output = Result<Vec<u8>, RenderError>;
+
+    fn render(&self, scene: &Scene<'_>) -> Self::Output {
+        let Scene { geometry, colors, config, .. } = scene;
+        let colors =
+            colors.iter().map(|color| parse_color_or_err(color)).collect::<Result<Vec<_>, _>>()?;
+        if config.legend != LegendPosition::None {
+            return Err(RenderError::InvalidConfig { field: "legend" });
+        }
+
+        let (min_x, min_y, width, height) = geometry.bounds;
+        let scale = f64::from(self.width) / width;
+        let (pixel_width, pixel_height) = (self.width, ((height * scale).round() as u32).max(1));
+        if pixel_width == 0
+            || pixel_width.max(pixel_height) > Self::MAX_SIZE
+            || u64::from(pixel_width) * u64::from(pixel_height) > Self::MAX_PIXELS
+        {
+            return Err(RenderError::InvalidImageSize { width: pixel_width, height: pixel_height });
+        }
+        let mut canvas = Canvas {
+            width: pixel_width as usize,
+            height: pixel_height as usize,
+            pixels: vec![[0.0; 4]; pixel_width as usize * pixel_height as usize],
+            origin: (min_x, min_y),
+            scale,
+        };
+
+        let outlines: Vec<Vec<Vec<Point>>> =
+            geometry.outlines.iter().map(|outline| canvas.outline(outline)).collect();
+        for polygons in &outlines {
+            canvas.fill(polygons, FillRule::EvenOdd, [0, 0, 0], 1.0);
+        }
+        for (polygons, &color) in outlines.iter().zip(&colors) {
+            canvas.fill(polygons, FillRule::EvenOdd, color, config.fill_opacity);
+        }
+        for (polygons, &color) in outlines.iter().zip(&colors) {
+            let strokes: Vec<Vec<Point>> =
+                polygons.iter().flat_map(|points| stroke(points, config.line_width)).collect();
+            canvas.fill(&strokes, FillRule::NonZero, color, 1.0);
+        }
+
+        for pie in &geometry.pies {
+            let circle = pie.circle(config);
+            let opacity = circle.map_or(1.0, |circle| circle.opacity);
+            let (cx, cy) = pie.center;
+            for slice in &pie.slices {
+                // Slices start straight up, but angles start to the right
+                let (start, end) = (slice.start - FRAC_PI_2, slice.end - FRAC_PI_2);
+                let mut points = vec![
+                    pie.center,
+                    (cx + pie.radius * start.cos(), cy + pie.radius * start.sin()),
+                ];
+                canvas.arc(&mut points, pie.center, pie.radius, start, end);
+                canvas.fill(&[points], FillRule::NonZero, colors[slice.set], opacity);
+            }
+            if let Some(circle) = circle {
+                let ring = [
+                    canvas.circle(pie.center, pie.radius + 0.0125),
+                    canvas.circle(pie.center, pie.radius - 0.0125),
+                ];
+                canvas.fill(&ring, FillRule::EvenOdd, parse_color_or_err(&circle.color)?, opacity);
+            }
+        }
+
+        Ok(encode_png(pixel_width, pixel_height, &canvas.rgba()))
+    }
+}
+
+impl Layout {
+    /// Render as a PNG image which is `width` pixels wide, see
+    /// [`PngRenderer`] and [`Layout::try_to_svg`].
+    ///
+    /// ```
+    /// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+    /// use venn_diagrams::svg::{DiagramConfig, Layout};
+    ///
+    /// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+    /// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+    /// let config = DiagramConfig::default();
+    /// let png = layout.to_png(400, &[0.3, 0.3, 0.4], colors, &config).unwrap();
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    pub fn to_png(
+        &self,
+        width: u32,
+        values: &[f64],
+        colors: &[&str],
+        config: &DiagramConfig,
+    ) -> Result<Vec<u8>, RenderError> {
+        PngRenderer { width }.render(&self.scene(values, colors, config)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blank(width: usize, height: usize) -> Canvas {
+        let pixels = vec![[0.0; 4]; width * height];
+        Canvas { width, height, pixels, origin: (0.0, 0.0), scale: 1.0 }
+    }
+
+    #[test]
+    fn coverage() {
+        // A square from the middle of pixel 1 to the middle of pixel 3
+        let mut canvas = blank(5, 5);
+        let square = vec![(1.5, 1.5), (3.5, 1.5), (3.5, 3.5), (1.5, 3.5)];
+        canvas.fill(std::slice::from_ref(&square), FillRule::NonZero, [255, 0, 0], 1.0);
+        let alpha = |x: usize, y: usize| canvas.pixels[y * 5 + x][3];
+        assert_eq!(alpha(0, 0), 0.0);
+        assert!((alpha(2, 2) - 1.0).abs() < 1e-9);
+        assert!((alpha(1, 2) - 0.5).abs() < 1e-9);
+        assert!((alpha(1, 1) - 0.25).abs() < 1e-9);
+
+        // With the even-odd rule, the overlap of two squares is a hole
+        let mut canvas = blank(5, 5);
+        let inner = vec![(2.0, 2.0), (3.0, 2.0), (3.0, 3.0), (2.0, 3.0)];
+        canvas.fill(&[square, inner], FillRule::EvenOdd, [255, 0, 0], 0.5);
+        assert_eq!(canvas.pixels[2 * 5 + 2][3], 0.0);
+        assert_eq!(canvas.rgba()[4 * (3 * 5 + 3)..][..4], [255, 0, 0, 32]);
+    }
+
+    #[test]
+    fn strokes() {
+        // The stroke of a square covers a band around its border, with sharp
+        // corners
+        let square = [(1.0, 1.0), (4.0, 1.0), (4.0, 4.0), (1.0, 4.0)];
+        let mut canvas = blank(5, 5);
+        canvas.fill(&stroke(&square, 1.0), FillRule::NonZero, [0, 0, 0], 1.0);
+        let alpha = |x: usize, y: usize| canvas.pixels[y * 5 + x][3];
+        assert!((alpha(0, 0) - 0.25).abs() < 1e-9);
+        assert!((alpha(2, 1) - 0.5).abs() < 1e-9);
+        assert_eq!(alpha(2, 2), 0.0);
+    }
+
+    #[test]
+    fn sizes() {
+        let layout: Layout = crate::constants::d3::LAYOUT_OPTIMIZED_THREE.into();
+        let colors = &["red", "green", "blue"];
+        let config = DiagramConfig::default();
+        let png = |width| layout.to_png(width, &[0.3, 0.3, 0.4], colors, &config);
+        assert!(png(1).is_ok());
+        assert!(matches!(png(0), Err(RenderError::InvalidImageSize { width: 0, .. })));
+        let error = png(PngRenderer::MAX_SIZE + 1).unwrap_err();
+        assert!(matches!(error, RenderError::InvalidImageSize { width: 16385, .. }));
+        // Every side is small enough, but not the number of pixels
+        let error = png(PngRenderer::MAX_SIZE).unwrap_err();
+        assert!(matches!(error, RenderError::InvalidImageSize { width: 16384, .. }));
+    }
+
+    #[test]
+    fn legend() {
+        let layout: Layout = crate::constants::d3::LAYOUT_OPTIMIZED_THREE.into();
+        let config = DiagramConfig { legend: LegendPosition::Bottom, ..DiagramConfig::default() };
+        let png = layout.to_png(100, &[0.3, 0.3, 0.4], &["red", "green", "blue"], &config);
+        assert_eq!(png, Err(RenderError::InvalidConfig { field: "legend" }));
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use svg::{
+    Document,
+    node::element::{Definitions, Mask, Path, Rectangle, SVG, Style, path::Data},
+};
+
+use super::{
+    DiagramConfig, Geometry, Layout, Outline, Segment, Styling,
+    circles::draw_circle,
+    legend::{draw_legend, legend_entries},
+};
+use crate::{coalition::Seats, diagram::SetMask};
+
+// The 64-bit FNV-1a hash, which unlike `DefaultHasher` is the same on every
+// platform and version of Rust
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// A number with at most 4 decimals, for formats which are written as text
+pub(super) fn num(x: f64) -> String {
+    let out = format!("{x:.4}");
+    let out = out.trim_end_matches('0').trim_end_matches('.');
+    if out == "-0" { String::from("0") } else { String::from(out) }
+}
+
+/// Everything needed to draw a diagram, see [`Layout::scene`].
+#[derive(Debug, Clone)]
+pub struct Scene<'a> {
+    /// The layout which is drawn.
+    pub layout: &'a Layout,
+
+    /// The outlines and pie charts of the layout.
+    pub geometry: Geometry,
+
+    /// The share of the total of each set.
+    pub values: Vec<f64>,
+
+    /// The seat counts, if the diagram is drawn from them.
+    pub seats: Option<&'a Seats>,
+
+    /// The color of each set.
+    pub colors: &'a [&'a str],
+
+    /// How the diagram is drawn.
+    pub config: &'a DiagramConfig,
+}
+
+/// An output format for diagrams.
+///
+/// ```
+/// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+/// use venn_diagrams::svg::{DiagramConfig, Layout, Renderer, Scene};
+///
+/// // Lists the subset of each pie chart
+/// struct Subsets;
+///
+/// impl Renderer for Subsets {
+///     type Output = Vec<u32>;
+///
+///     fn render(&self, scene: &Scene<'_>) -> Vec<u32> {
+///         scene.geometry.pies.iter().map(|pie| pie.subset).collect()
+///     }
+/// }
+///
+/// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+/// let colors = &["MediumVioletRed", "DarkOrange", "DeepSkyBlue"];
+/// let config = DiagramConfig::default();
+/// let scene = layout.scene(&[0.3, 0.3, 0.4], colors, &config).unwrap();
+/// assert_eq!(Subsets.render(&scene).len(), 7);
+/// ```
+pub trait Renderer {
+    /// The rendered diagram.
+    type Output;
+
+    /// Draw the diagram.
+    fn render(&self, scene: &Scene<'_>) -> Self::Output;
+}
+
+/// Draws diagrams as SVGs, see [`Layout::to_svg`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SvgRenderer;
+
+// The SVG path of an outline, without any attributes
+fn outline_path(outline: &Outline) -> Path {
+    let mut data = Data::new();
+    for cycle in &outline.cycles {
+        data = data.move_to(cycle.start);
+        for segment in &cycle.segments {
+            data = match *segment {
+                Segment::Line { to } => data.line_to(to),
+                Segment::Arc { to, radius, clockwise } => {
+                    data.elliptical_arc_to((radius, radius, 0, 0, i32::from(clockwise), to.0, to.1))
+                }
+            };
+        }
+        data = data.close();
+    }
+    Path::new().set("d", data)
+}
+
+impl Renderer for SvgRenderer {
+    type Output = SVG;
+
+    fn render(&self, scene: &Scene<'_>) -> SVG {
+        let Scene { layout, geometry, values, seats, colors, config } = scene;
+        let paths: Vec<Path> = geometry.outlines.iter().map(outline_path).collect();
+
+        let (min_x, min_y, mut total_width, mut total_height) = geometry.bounds;
+
+        let entries = legend_entries(scene);
+        let legend = draw_legend(
+            &entries,
+            config.legend,
+            config.styling,
+            (min_x, min_y, total_width, total_height),
+        );
+        if let Some(legend) = &legend {
+            total_width += legend.width;
+            total_height += legend.height;
+        }
+
+        let mut out = Document::new().set("viewBox", (min_x, min_y, total_width, total_height));
+
+        if let Some(width_mul) = config.width_mul {
+            out = out.set("width", format!("{}px", width_mul * total_width));
+        }
+
+        if let Some(height_mul) = config.height_mul {
+            out = out.set("height", format!("{}px", height_mul * total_width));
+        }
+
+        // Every id is unique to the content of the SVG, so that multiple SVGs
+        // can be on the same page. Identical SVGs share their ids, which keeps
+        // the output deterministic.
+        let id = match config.id {
+            Some(id) => id.to_string(),
+            None => format!(
+                "{:016x}",
+                fnv1a(format!("{layout:?}{values:?}{seats:?}{colors:?}{config:?}").as_bytes())
+            ),
+        };
+
+        // The id of the SVG itself scopes stylesheets to this diagram
+        if config.styling.uses_classes() {
+            out = out.set("id", format!("venn-{id}"));
+        }
+        if config.styling == Styling::Stylesheet {
+            out = out.add(Style::new(config.scoped_stylesheet(colors, &format!("#venn-{id} "))));
+        }
+
+        let mask_id = format!("background_mask_{id}");
+
+        let mut mask = Mask::new().set("id", mask_id.as_str());
+
+        for path in &paths {
+            let part =
+                path.clone().set("fill", "white").set("fill-rule", "evenodd").set("stroke", "none");
+            mask = mask.add(part);
+        }
+
+        out = out.add(Definitions::new().add(mask));
+
+        let classes = config.styling.uses_classes();
+        let rect = Rectangle::new()
+            .set("width", total_width)
+            .set("height", total_height)
+            .set("x", min_x)
+            .set("y", min_y)
+            .set("mask", format!("url(#{mask_id})"));
+        let rect = if classes { rect.set("class", "venn-background") } else { rect };
+
+        out = out.add(rect);
+
+        for (i, (path, color)) in paths.iter().zip(*colors).enumerate() {
+            let path = path.clone().set("fill-rule", "evenodd");
+            let path = if classes {
+                path.set("class", format!("venn-set venn-set-{i} venn-fill"))
+            } else {
+                path.set("fill", *color)
+                    .set("fill-opacity", config.fill_opacity)
+                    .set("stroke", "none")
+            };
+            out = out.add(path);
+        }
+
+        for (i, (path, color)) in paths.iter().zip(*colors).enumerate() {
+            let path = if classes {
+                path.clone().set("class", format!("venn-set venn-set-{i} venn-border"))
+            } else {
+                path.clone()
+                    .set("fill", "none")
+                    .set("stroke", *color)
+                    .set("stroke-width", config.line_width)
+            };
+            out = out.add(path);
+        }
+
+        // Draw the pie charts, with ids based on their subset, which are
+        // numbered if a subset is part of several cells
+        let mut id_counts: HashMap<SetMask, usize> = HashMap::new();
+        for pie in &geometry.pies {
+            let count = id_counts.entry(pie.subset).or_default();
+            let mut region_id = format!("region_{id}_{}", pie.subset);
+            if *count > 0 {
+                region_id = format!("{region_id}_{count}");
+            }
+            *count += 1;
+
+            out = out.add(draw_circle(pie, scene).set("id", region_id));
+        }
+
+        if let Some(legend) = legend {
+            out = out.add(legend.group);
+        }
+
+        out
+    }
+}
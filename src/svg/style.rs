@@ -0,0 +1,104 @@
+use std::fmt::Write;
+
+use super::DiagramConfig;
+
+/// How the colors, opacities and stroke widths of a diagram are set, see
+/// [`DiagramConfig`].
+///
+/// With classes, the elements get the following classes, where sets are
+/// numbered from 0 like in a [`SetMask`](crate::diagram::SetMask):
+/// - `venn-set` and `venn-set-{i}` on everything drawn for set `i`, together
+///   with `venn-fill` on the inside of its polyomino, `venn-border` on its
+///   border, `venn-piece` on its pieces of the pie charts and `venn-swatch` on
+///   its color in the legend.
+/// - `venn-region` and `venn-coalition-{below,edge,above}` on each pie chart,
+///   see [`Coalition`](crate::coalition::Coalition), together with
+///   `venn-band-{k}` or `venn-band-{k}-edge` if the circle around it is
+///   `circle` or `circle_edge` of band `k` of
+///   [`DiagramConfig::thresholds`].
+/// - `venn-circle` on each circle around a pie chart.
+/// - `venn-legend-text` on the texts of the legend.
+/// - `venn-background` on the black background behind the polyominos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Styling {
+    /// Set everything as attributes of each element.
+    #[default]
+    Inline,
+
+    /// Only set classes, so that the diagram must be styled by an external
+    /// stylesheet, such as the one from [`DiagramConfig::stylesheet`].
+    Classes,
+
+    /// Set classes, and embed the stylesheet from
+    /// [`DiagramConfig::stylesheet`] in the SVG. Its selectors only match
+    /// inside the SVG, which has the id `venn-{id}` as described in
+    /// [`DiagramConfig::id`], so that differently styled diagrams can be on
+    /// the same page.
+    Stylesheet,
+}
+
+impl Styling {
+    // Whether elements get classes instead of attributes
+    pub(super) fn uses_classes(self) -> bool {
+        self != Self::Inline
+    }
+}
+
+impl DiagramConfig {
+    /// A stylesheet for [`Styling::Classes`], which makes the diagram look
+    /// like it does with [`Styling::Inline`], where set `i` has the color
+    /// `colors[i]`.
+    ///
+    /// ```
+    /// use venn_diagrams::svg::DiagramConfig;
+    ///
+    /// let css = DiagramConfig::default().stylesheet(&["red", "blue"]);
+    /// assert!(css.contains(".venn-set-1 { fill: blue; stroke: blue; }"));
+    /// ```
+    #[must_use]
+    pub fn stylesheet(&self, colors: &[&str]) -> String {
+        self.scoped_stylesheet(colors, "")
+    }
+
+    // The stylesheet where every selector starts with `scope`, such as
+    // `#venn-1 `, so that it only applies to one diagram
+    pub(super) fn scoped_stylesheet(&self, colors: &[&str], scope: &str) -> String {
+        // Writing to a `String` never fails
+        let mut out = String::new();
+        let w = &mut out;
+        for (i, color) in colors.iter().enumerate() {
+            writeln!(w, "{scope}.venn-set-{i} {{ fill: {color}; stroke: {color}; }}").unwrap();
+        }
+        let fill_opacity = self.fill_opacity;
+        writeln!(w, "{scope}.venn-fill {{ fill-opacity: {fill_opacity}; stroke: none; }}").unwrap();
+        let line_width = self.line_width;
+        writeln!(w, "{scope}.venn-border {{ fill: none; stroke-width: {line_width}; }}").unwrap();
+        writeln!(w, "{scope}.venn-background {{ fill: black; }}").unwrap();
+        writeln!(w, "{scope}.venn-piece, {scope}.venn-swatch {{ stroke: none; }}").unwrap();
+        writeln!(w, "{scope}.venn-circle {{ fill: transparent; stroke-width: 0.025; }}").unwrap();
+        for (k, band) in self.thresholds.iter().enumerate() {
+            let circles = [
+                (format!("venn-band-{k}"), Some(&band.circle)),
+                (format!("venn-band-{k}-edge"), band.circle_edge.as_ref()),
+            ];
+            for (class, circle) in circles {
+                let Some(circle) = circle else {
+                    continue;
+                };
+                writeln!(w, "{scope}.{class} {{ opacity: {}; }}", circle.opacity).unwrap();
+                writeln!(w, "{scope}.{class} .venn-circle {{ stroke: {}; }}", circle.color)
+                    .unwrap();
+            }
+        }
+        writeln!(
+            w,
+            "{scope}.venn-legend-text {{ font-size: {}px; font-family: sans-serif; \
+             dominant-baseline: central; }}",
+            super::legend::FONT_SIZE
+        )
+        .unwrap();
+        out
+    }
+}
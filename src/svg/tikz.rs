@@ -0,0 +1,235 @@
+use std::fmt::Write;
+
+use super::{
+    Outline, Point, RenderError, Renderer, Scene,
+    color::parse_color,
+    geometry::Resolved,
+    legend::{Placement, ROW_HEIGHT, SPACING, SWATCH, legend_entries, place_legend},
+    render::num,
+};
+
+/// Draws diagrams as TikZ pictures for LaTeX documents, see [`Renderer`].
+///
+/// The output is a `tikzpicture` environment, where the color of set `i` is
+/// named `venn-set-{i}` and the colors of the circles of band `k` of
+/// [`DiagramConfig::thresholds`](super::DiagramConfig::thresholds) are named
+/// `venn-band-{k}` and `venn-band-{k}-edge`. Names and hex codes of CSS are
+/// converted, so that they look like in the other formats. Other names are
+/// left to xcolor, such as `Periwinkle` with `\usepackage[dvipsnames]{xcolor}`,
+/// and anything else gives [`RenderError::InvalidColor`]. The legend uses the
+/// font of the document.
+///
+/// ```
+/// use venn_diagrams::constants::d3::LAYOUT_OPTIMIZED_THREE;
+/// use venn_diagrams::svg::{DiagramConfig, Layout, Renderer, TikzRenderer};
+///
+/// let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+/// let colors = &["#C71585", "#FF8C00", "#00BFFF"];
+/// let config = DiagramConfig::default();
+/// let scene = layout.scene(&[0.3, 0.3, 0.4], colors, &config).unwrap();
+/// let tikz = TikzRenderer::default().render(&scene).unwrap();
+/// assert!(tikz.starts_with("\\begin{tikzpicture}"));
+/// assert!(tikz.contains("\\definecolor{venn-set-0}{RGB}{199,21,133}"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TikzRenderer {
+    /// Width of a cell in centimeters.
+    pub unit: f64,
+}
+
+impl Default for TikzRenderer {
+    fn default() -> Self {
+        Self { unit: 1.0 }
+    }
+}
+
+fn point((x, y): Point) -> String {
+    format!("({},{})", num(x), num(y))
+}
+
+// Define a color of CSS by its red, green and blue, since xcolor has colors
+// with the same names but other values, or refer to another name of xcolor
+fn define_color(name: &str, color: &str) -> Result<String, RenderError> {
+    if let Some([r, g, b]) = parse_color(color) {
+        Ok(format!("\\definecolor{{{name}}}{{RGB}}{{{r},{g},{b}}}"))
+    } else if !color.is_empty() && color.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(format!("\\colorlet{{{name}}}{{{color}}}"))
+    } else {
+        Err(RenderError::InvalidColor { color: String::from(color) })
+    }
+}
+
+// Escape the characters which are special in TeX
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// The TikZ path of an outline
+fn outline_path(outline: &Outline) -> String {
+    let mut out = String::new();
+    for cycle in &outline.cycles {
+        out += &point(cycle.start);
+        for segment in cycle.resolved() {
+            match segment {
+                Resolved::Arc { radius, start, end, .. } => {
+                    write!(
+                        out,
+                        " arc[start angle={}, end angle={}, radius={}]",
+                        num(start.to_degrees()),
+                        num(end.to_degrees()),
+                        num(radius)
+                    )
+                    .unwrap();
+                }
+                Resolved::Line { to } => out += &format!(" -- {}", point(to)),
+            }
+        }
+        out += " -- cycle ";
+    }
+    out.pop();
+    out
+}
+
+impl Renderer for TikzRenderer {
+    type Output = Result<String, RenderError>;
+
+    fn render(&self, scene: &Scene<'_>) -> Self::Output {
+        let Scene { geometry, colors, config, .. } = scene;
+        // Writing to a `String` never fails
+        let mut out = String::new();
+        let w = &mut out;
+        let unit = num(self.unit);
+        writeln!(w, "\\begin{{tikzpicture}}[x={unit}cm, y=-{unit}cm]").unwrap();
+
+        for (i, color) in colors.iter().enumerate() {
+            writeln!(w, "{}", define_color(&format!("venn-set-{i}"), color)?).unwrap();
+        }
+        for (k, band) in config.thresholds.iter().enumerate() {
+            let circle = define_color(&format!("venn-band-{k}"), &band.circle.color)?;
+            writeln!(w, "{circle}").unwrap();
+            if let Some(circle) = &band.circle_edge {
+                let circle = define_color(&format!("venn-band-{k}-edge"), &circle.color)?;
+                writeln!(w, "{circle}").unwrap();
+            }
+        }
+
+        let paths: Vec<String> = geometry.outlines.iter().map(outline_path).collect();
+        for path in &paths {
+            writeln!(w, "\\fill[black, even odd rule] {path};").unwrap();
+        }
+        for (i, path) in paths.iter().enumerate() {
+            let opacity = num(config.fill_opacity);
+            writeln!(w, "\\fill[venn-set-{i}, fill opacity={opacity}, even odd rule] {path};")
+                .unwrap();
+        }
+        let line_width = num(config.line_width * self.unit);
+        for (i, path) in paths.iter().enumerate() {
+            writeln!(w, "\\draw[venn-set-{i}, line width={line_width}cm] {path};").unwrap();
+        }
+
+        for pie in &geometry.pies {
+            let circle = pie.circle(config);
+            let opacity = circle.map_or(1.0, |circle| circle.opacity);
+            if opacity != 1.0 {
+                writeln!(w, "\\begin{{scope}}[opacity={}]", num(opacity)).unwrap();
+            }
+            let (cx, cy) = pie.center;
+            let r = pie.radius;
+            for slice in &pie.slices {
+                // Slices start straight up, but TikZ angles start to the right
+                let (start, end) = (slice.start.to_degrees() - 90.0, slice.end.to_degrees() - 90.0);
+                let from = (cx + slice.start.sin() * r, cy - slice.start.cos() * r);
+                writeln!(
+                    w,
+                    "\\fill[venn-set-{}] {} -- {} arc[start angle={}, end angle={}, radius={}] -- cycle;",
+                    slice.set,
+                    point(pie.center),
+                    point(from),
+                    num(start),
+                    num(end),
+                    num(r)
+                )
+                .unwrap();
+            }
+            if let Some(band) = pie.band {
+                let edge = if pie.edge { "-edge" } else { "" };
+                let width = num(0.025 * self.unit);
+                writeln!(
+                    w,
+                    "\\draw[venn-band-{band}{edge}, line width={width}cm] {} circle[radius={}];",
+                    point(pie.center),
+                    num(r)
+                )
+                .unwrap();
+            }
+            if opacity != 1.0 {
+                writeln!(w, "\\end{{scope}}").unwrap();
+            }
+        }
+
+        // Leave the same margins as the SVG, which the legend may extend
+        let (min_x, min_y, width, height) = geometry.bounds;
+        let entries = legend_entries(scene);
+        let legend = place_legend(&entries, config.legend, geometry.bounds);
+        let (legend_width, legend_height) =
+            legend.as_ref().map_or((0.0, 0.0), |l| (l.width, l.height));
+        writeln!(
+            w,
+            "\\path {} rectangle {};",
+            point((min_x, min_y)),
+            point((min_x + width + legend_width, min_y + height + legend_height))
+        )
+        .unwrap();
+
+        if let Some(Placement { positions, .. }) = legend {
+            for (i, ((_, text), (x, y))) in entries.iter().zip(positions).enumerate() {
+                let swatch = (x, y + (ROW_HEIGHT - SWATCH) / 2.0);
+                let corner = (swatch.0 + SWATCH, swatch.1 + SWATCH);
+                writeln!(w, "\\fill[venn-set-{i}] {} rectangle {};", point(swatch), point(corner))
+                    .unwrap();
+                let at = (x + SWATCH + SPACING, y + ROW_HEIGHT / 2.0);
+                writeln!(
+                    w,
+                    "\\node[anchor=west, inner sep=0] at {} {{{}}};",
+                    point(at),
+                    escape(text)
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(w, "\\end{{tikzpicture}}").unwrap();
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_and_text() {
+        let define = |color| define_color("a", color).unwrap();
+        assert_eq!(define("#ee2020"), "\\definecolor{a}{RGB}{238,32,32}");
+        assert_eq!(define("#e20"), "\\definecolor{a}{RGB}{238,34,0}");
+        // CSS and xcolor disagree about green
+        assert_eq!(define("green"), "\\definecolor{a}{RGB}{0,128,0}");
+        assert_eq!(define("Periwinkle"), "\\colorlet{a}{Periwinkle}");
+        let error = RenderError::InvalidColor { color: String::from("rgb(1,2,3)") };
+        assert_eq!(define_color("a", "rgb(1,2,3)"), Err(error));
+        assert_eq!(escape("S&D 30.5%"), "S\\&D 30.5\\%");
+    }
+}
@@ -0,0 +1,13 @@
+use venn_diagrams::{codegen, constants::d3::LAYOUT_OPTIMIZED_THREE, svg::Layout};
+
+mod generated;
+
+#[test]
+fn three() {
+    let layout: Layout = LAYOUT_OPTIMIZED_THREE.into();
+    let code = codegen::module("THREE", "LAYOUT_THREE", &layout).unwrap();
+    assert_eq!(code, include_str!("generated/three.rs"));
+
+    let generated: Layout = generated::three::LAYOUT_THREE.into();
+    assert_eq!(generated, layout);
+}
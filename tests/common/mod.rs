@@ -19,7 +19,8 @@ pub fn normalize(values: &[f64]) -> Vec<f64> {
 // We use a macro so that when `assert_binary_snapshot` adds the current module
 // to the file name, it doesn't include this module.
 macro_rules! compare_snapshot {
-    ($name:expr, $svg:expr) => {
+    // For formats which aren't text
+    ($name:expr, bytes $bytes:expr) => {
         use std::path::PathBuf;
         let mut settings = insta::Settings::clone_current();
         let root_dir = std::env::var("CARGO_MANIFEST_DIR")
@@ -31,10 +32,10 @@ macro_rules! compare_snapshot {
         settings.set_snapshot_path(path);
 
         settings.bind(|| {
-            insta::assert_binary_snapshot!(
-                $name,
-                $svg.to_string().as_bytes().into_iter().cloned().collect()
-            );
+            insta::assert_binary_snapshot!($name, $bytes);
         });
     };
+    ($name:expr, $svg:expr) => {
+        compare_snapshot!($name, bytes $svg.to_string().as_bytes().into_iter().cloned().collect())
+    };
 }
@@ -0,0 +1,39 @@
+//! Venn diagram for 3 sets.
+//!
+//! The diagram itself is [`THREE`], and its layout is [`LAYOUT_THREE`].
+
+use venn_diagrams::{
+    diagram::DiagramConst,
+    svg::{
+        DirectedEdge::{Horizontal, Vertical},
+        LayoutConst,
+    },
+};
+
+/// A Venn diagram for 3 sets.
+pub const THREE: DiagramConst<3, 5, 2> = DiagramConst::from_binary_str(GRIDS);
+
+#[rustfmt::skip]
+const GRIDS: [[&str; 2]; 3] = [
+    [
+        "11100",
+        "00100",
+    ],
+    [
+        "01110",
+        "00010",
+    ],
+    [
+        "00100",
+        "00111",
+    ],
+];
+
+/// A layout for [`THREE`].
+#[rustfmt::skip]
+pub const LAYOUT_THREE: LayoutConst<18, 3, 5, 2> = LayoutConst::new(
+    [Vertical { x: 0, y_from: 1, y_to: 0 }, Horizontal { y: 0, x_from: 0, x_to: 3 }, Vertical { x: 3, y_from: 0, y_to: 2 }, Horizontal { y: 2, x_from: 3, x_to: 2 }, Vertical { x: 2, y_from: 2, y_to: 1 }, Horizontal { y: 1, x_from: 2, x_to: 0 }, Vertical { x: 1, y_from: 1, y_to: 0 }, Horizontal { y: 0, x_from: 1, x_to: 4 }, Vertical { x: 4, y_from: 0, y_to: 2 }, Horizontal { y: 2, x_from: 4, x_to: 3 }, Vertical { x: 3, y_from: 2, y_to: 1 }, Horizontal { y: 1, x_from: 3, x_to: 1 }, Vertical { x: 2, y_from: 2, y_to: 0 }, Horizontal { y: 0, x_from: 2, x_to: 3 }, Vertical { x: 3, y_from: 0, y_to: 1 }, Horizontal { y: 1, x_from: 3, x_to: 5 }, Vertical { x: 5, y_from: 1, y_to: 2 }, Horizontal { y: 2, x_from: 5, x_to: 2 }],
+    [0, -1, 0, -1, 1, 0, 0, 0, 0, -1, 1, -1, 0, 1, -1, 0, 0, 0],
+    [6, 6, 6],
+    THREE,
+);
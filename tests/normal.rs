@@ -1,7 +1,11 @@
 use venn_diagrams::{
+    coalition::{Game, Seats},
     constants::{self, d3::THREE},
-    diagram::Diagram,
-    svg::{CornerStyle, DiagramConfig},
+    diagram::{Diagram, construct_blocks},
+    svg::{
+        CornerStyle, DiagramConfig, EpsRenderer, Layout, LegendPosition, PngRenderer, Renderer,
+        Styling, TikzRenderer,
+    },
 };
 
 use crate::common::{COLORS, VALUES, normalize};
@@ -78,3 +82,143 @@ fn three_docs() {
     let svg = paths.to_svg(values, colors, &DiagramConfig::default());
     compare_snapshot!("three_docs.svg", svg);
 }
+
+#[test]
+fn holes() {
+    let venn = Diagram::from_binary_grids(&[
+        "11111\n10001\n10101\n10001\n11111",
+        "00000\n01110\n01010\n01110\n00000",
+        "00000\n00000\n00100\n00011\n00011",
+    ])
+    .unwrap();
+    test_venn_greedy("holes.svg", venn, &DiagramConfig::default());
+}
+
+#[test]
+fn constructed_six() {
+    test_venn_greedy(
+        "constructed_six.svg",
+        construct_blocks(6).unwrap(),
+        &DiagramConfig::default(),
+    );
+}
+
+#[test]
+fn six_rotated() {
+    let layout: Layout = constants::d6::LAYOUT_OPTIMIZED_SIX.into();
+    let values = normalize(&VALUES[0..6]);
+    let svg = layout.rotate90().to_svg(&values, &COLORS[0..6], &DiagramConfig::default());
+    compare_snapshot!("six_rotated.svg", svg);
+}
+
+#[test]
+fn seven_permuted() {
+    let layout: Layout = constants::d7::LAYOUT_OPTIMIZED_SEVEN.into();
+    let values = normalize(&VALUES[0..7]);
+    let game = Game::new(values.clone(), 0.5);
+    let permutation = Diagram::from(constants::d7::SEVEN).best_permutation(&game);
+    let layout = layout.permute_sets(&permutation);
+    let svg = layout.to_svg(&values, &COLORS[0..7], &DiagramConfig::default());
+    compare_snapshot!("seven_permuted.svg", svg);
+}
+
+#[test]
+fn eight_seats() {
+    let layout: Layout = constants::d8::LAYOUT_OPTIMIZED_EIGHT.into();
+    let seats = Seats::new(VALUES.iter().map(|&x| x as u64).collect());
+    let svg = layout.to_svg_seats(&seats, &COLORS, &DiagramConfig::default());
+    compare_snapshot!("eight_seats.svg", svg);
+}
+
+const NAMES: [&str; 8] = ["S", "SD", "M", "V", "C", "KD", "MP", "L"];
+
+#[test]
+fn four_legend_right() {
+    let layout: Layout = constants::d4::LAYOUT_OPTIMIZED_FOUR.into();
+    let values = normalize(&VALUES[0..4]);
+    let config = DiagramConfig {
+        legend: LegendPosition::Right,
+        names: NAMES[0..4].iter().map(|name| String::from(*name)).collect(),
+        ..DiagramConfig::default()
+    };
+    let svg = layout.to_svg(&values, &COLORS[0..4], &config);
+    compare_snapshot!("four_legend_right.svg", svg);
+}
+
+#[test]
+fn eight_legend_bottom() {
+    let layout: Layout = constants::d8::LAYOUT_OPTIMIZED_EIGHT.into();
+    let seats = Seats::new(VALUES.iter().map(|&x| x as u64).collect());
+    let config = DiagramConfig {
+        legend: LegendPosition::Bottom,
+        names: NAMES.iter().map(|name| String::from(*name)).collect(),
+        ..DiagramConfig::default()
+    };
+    let svg = layout.to_svg_seats(&seats, &COLORS, &config);
+    compare_snapshot!("eight_legend_bottom.svg", svg);
+}
+
+#[test]
+fn three_stylesheet() {
+    let layout: Layout = constants::d3::LAYOUT_OPTIMIZED_THREE.into();
+    let values = normalize(&VALUES[0..3]);
+    let config = DiagramConfig {
+        styling: Styling::Stylesheet,
+        legend: LegendPosition::Right,
+        ..DiagramConfig::default()
+    };
+    let svg = layout.to_svg(&values, &COLORS[0..3], &config);
+    compare_snapshot!("three_stylesheet.svg", svg);
+}
+
+#[test]
+fn four_tikz() {
+    let layout: Layout = constants::d4::LAYOUT_OPTIMIZED_FOUR.into();
+    let values = normalize(&VALUES[0..4]);
+    let config = DiagramConfig {
+        legend: LegendPosition::Right,
+        names: NAMES[0..4].iter().map(|name| String::from(*name)).collect(),
+        ..DiagramConfig::default()
+    };
+    let scene = layout.scene(&values, &COLORS[0..4], &config).unwrap();
+    compare_snapshot!("four_tikz.tex", TikzRenderer::default().render(&scene).unwrap());
+}
+
+#[test]
+fn four_eps() {
+    let layout: Layout = constants::d4::LAYOUT_OPTIMIZED_FOUR.into();
+    let values = normalize(&VALUES[0..4]);
+    let config = DiagramConfig {
+        legend: LegendPosition::Right,
+        names: NAMES[0..4].iter().map(|name| String::from(*name)).collect(),
+        ..DiagramConfig::default()
+    };
+    let scene = layout.scene(&values, &COLORS[0..4], &config).unwrap();
+    compare_snapshot!("four_eps.eps", EpsRenderer::default().render(&scene).unwrap());
+}
+
+#[test]
+fn three_png() {
+    let layout: Layout = constants::d3::LAYOUT_OPTIMIZED_THREE.into();
+    let values = normalize(&VALUES[0..3]);
+    let png = layout.to_png(300, &values, &COLORS[0..3], &DiagramConfig::default()).unwrap();
+    compare_snapshot!("three_png.png", bytes png);
+}
+
+#[test]
+fn four_png() {
+    let layout: Layout = constants::d4::LAYOUT_OPTIMIZED_FOUR.into();
+    let values = normalize(&VALUES[0..4]);
+    let png = layout.to_png(300, &values, &COLORS[0..4], &DiagramConfig::default()).unwrap();
+    compare_snapshot!("four_png.png", bytes png);
+}
+
+#[test]
+fn eight_seats_png() {
+    let layout: Layout = constants::d8::LAYOUT_OPTIMIZED_EIGHT.into();
+    let seats = Seats::new(VALUES.iter().map(|&x| x as u64).collect());
+    let config = DiagramConfig::default();
+    let scene = layout.scene_seats(&seats, &COLORS, &config).unwrap();
+    let png = PngRenderer { width: 400 }.render(&scene).unwrap();
+    compare_snapshot!("eight_seats_png.png", bytes png);
+}
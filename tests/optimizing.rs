@@ -1,6 +1,9 @@
 #![cfg(feature = "optimize")]
 
-use venn_diagrams::{constants, diagram::Diagram};
+use venn_diagrams::{
+    constants,
+    diagram::{self, Diagram, DiagramError, SearchConfig, Symmetry},
+};
 
 mod common;
 
@@ -48,3 +51,57 @@ fn eight() {
     let diagram: Diagram = constants::d8::EIGHT.into();
     assert!(diagram.layout_optimize() == constants::d8::LAYOUT_OPTIMIZED_EIGHT.into());
 }
+
+#[test]
+fn search() {
+    let mut config = SearchConfig::new(3, 4, 2);
+    config.limit = 4;
+    let diagrams = diagram::search(&config).unwrap();
+    assert_eq!(diagrams.len(), 4);
+    for (i, diagram) in diagrams.iter().enumerate() {
+        assert!(diagram.validate().is_valid());
+        assert!(diagrams[..i].iter().all(|x| x != diagram));
+    }
+
+    // Rotate180 requires an even number of sets
+    config.convex = true;
+    config.symmetry = Some(Symmetry::Rotate180);
+    assert!(diagram::search(&config).unwrap().is_empty());
+
+    assert!(diagram::search(&SearchConfig::new(3, 3, 2)).unwrap().is_empty());
+}
+
+#[test]
+fn search_symmetric() {
+    // `A AB B` and `B AB A` are the only symmetric diagrams in a row of three
+    let mut config = SearchConfig::new(2, 3, 1);
+    config.symmetry = Some(Symmetry::Rotate180);
+    config.convex = true;
+    config.limit = 4;
+    let diagrams = diagram::search(&config).unwrap();
+    assert_eq!(diagrams.len(), 2);
+    for diagram in &diagrams {
+        assert!(diagram.validate().is_valid());
+        assert_eq!(diagram.subset_at(1, 0), Some(0b11));
+        let swap = |subset: u32| ((subset & 1) << 1) | (subset >> 1);
+        assert_eq!(diagram.subset_at(2, 0).map(swap), diagram.subset_at(0, 0));
+    }
+    assert_ne!(diagrams[0], diagrams[1]);
+
+    // The cell containing both sets would have to be its own rotation, but
+    // a 2x2 box has no center cell
+    let mut config = SearchConfig::new(2, 2, 2);
+    config.symmetry = Some(Symmetry::Rotate180);
+    assert!(diagram::search(&config).unwrap().is_empty());
+    config.symmetry = None;
+    assert_eq!(diagram::search(&config).unwrap().len(), 1);
+}
+
+#[test]
+fn search_invalid() {
+    let search = |n, width, height| diagram::search(&SearchConfig::new(n, width, height));
+    assert_eq!(search(0, 2, 2), Err(DiagramError::NoSets));
+    assert_eq!(search(64, 1 << 20, 1 << 20), Err(DiagramError::TooManySets { n: 64 }));
+    let error = DiagramError::TooLarge { width: usize::MAX, height: 2 };
+    assert_eq!(search(3, usize::MAX, 2), Err(error));
+}